@@ -0,0 +1,67 @@
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+lazy_static! {
+    // Reading every note's frontmatter is expensive, so the map is kept
+    // until `invalidate` is called or the vault moves
+    static ref ALIAS_CACHE: Mutex<Option<(PathBuf, HashMap<String, String>)>> = Mutex::new(None);
+}
+
+fn alias_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Map each lowercased frontmatter alias to the filename declaring it.
+/// Standalone notes win over daily notes when two declare the same alias.
+fn build_map() -> HashMap<String, String> {
+    let ignore_rules = crate::load_ignore_rules();
+    let standalone_dir = crate::get_standalone_dir();
+    let mut map = HashMap::new();
+
+    // Daily notes first so standalone notes overwrite them
+    let mut paths: Vec<PathBuf> = crate::list_note_paths(true)
+        .into_iter()
+        .chain(crate::list_note_paths(false))
+        .filter(|path| !crate::is_ignored(&ignore_rules, path))
+        .collect();
+    paths.sort_by_key(|path| path.starts_with(&standalone_dir));
+
+    for path in paths {
+        let Some(filename) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        for alias in crate::read_frontmatter(&path).aliases.unwrap_or_default() {
+            let key = alias_key(&alias);
+            if !key.is_empty() {
+                map.insert(key, filename.to_string());
+            }
+        }
+    }
+
+    map
+}
+
+/// Drop the cached map after notes may have gained, lost or moved aliases,
+/// so the next lookup rereads every note's frontmatter
+pub fn invalidate() {
+    if let Ok(mut cache) = ALIAS_CACHE.lock() {
+        *cache = None;
+    }
+}
+
+/// Resolve a wiki link name through note aliases, returning the filename of
+/// the note that declares it
+pub fn resolve(name: &str) -> Option<String> {
+    let vault = crate::get_notes_dir();
+    let mut cache = ALIAS_CACHE.lock().ok()?;
+
+    if !matches!(cache.as_ref(), Some((cached, _)) if *cached == vault) {
+        *cache = Some((vault, build_map()));
+    }
+
+    cache
+        .as_ref()
+        .and_then(|(_, map)| map.get(&alias_key(name)).cloned())
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn get_archive_dir() -> PathBuf {
+    crate::get_notes_dir().join("archive")
+}
+
+/// Archived notes keep their folder so they can be put back where they were
+fn subfolder(is_daily: bool) -> &'static str {
+    if is_daily {
+        "daily"
+    } else {
+        "notes"
+    }
+}
+
+fn is_plain_filename(filename: &str) -> bool {
+    Path::new(filename).file_name() == Some(std::ffi::OsStr::new(filename))
+}
+
+/// Move a note into the archive, returning its vault-relative archived path
+pub fn archive(path: &Path, is_daily: bool) -> Result<String, String> {
+    let folder = subfolder(is_daily);
+    let archive_dir = get_archive_dir().join(folder);
+    fs::create_dir_all(&archive_dir).map_err(|e| e.to_string())?;
+
+    let filename = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid note filename")?;
+    let archived_path = archive_dir.join(filename);
+    if archived_path.exists() {
+        return Err(format!(
+            "An archived note named '{}' already exists",
+            filename
+        ));
+    }
+
+    fs::rename(path, &archived_path).map_err(|e| e.to_string())?;
+
+    Ok(format!("archive/{}/{}", folder, filename))
+}
+
+/// Find an archived note by filename. Returns its path and whether it was a
+/// daily note; standalone notes are checked first, like `note_exists` does.
+pub fn find(filename: &str) -> Option<(PathBuf, bool)> {
+    if !is_plain_filename(filename) {
+        return None;
+    }
+
+    [false, true]
+        .into_iter()
+        .map(|is_daily| {
+            (
+                get_archive_dir().join(subfolder(is_daily)).join(filename),
+                is_daily,
+            )
+        })
+        .find(|(path, _)| path.exists())
+}
+
+/// Move an archived note back into the daily or standalone folder it came
+/// from, returning the restored path and whether it is a daily note
+pub fn unarchive(filename: &str) -> Result<(PathBuf, bool), String> {
+    let (archived_path, is_daily) =
+        find(filename).ok_or_else(|| format!("'{}' is not in the archive", filename))?;
+
+    let dir = if is_daily {
+        crate::get_daily_note_dir(filename)
+    } else {
+        crate::get_standalone_dir()
+    };
+    let restored_path = dir.join(filename);
+
+    if restored_path.exists() {
+        return Err(format!("A note named '{}' already exists", filename));
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::rename(&archived_path, &restored_path).map_err(|e| e.to_string())?;
+
+    Ok((restored_path, is_daily))
+}
+
+/// Every archived note's path and whether it was a daily note
+pub fn list() -> Vec<(PathBuf, bool)> {
+    let mut notes = Vec::new();
+
+    for is_daily in [true, false] {
+        let Ok(entries) = fs::read_dir(get_archive_dir().join(subfolder(is_daily))) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+                notes.push((path, is_daily));
+            }
+        }
+    }
+
+    notes.sort();
+    notes
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+const ASSETS_FOLDER: &str = "assets";
+
+pub fn get_assets_dir() -> PathBuf {
+    crate::get_notes_dir().join(ASSETS_FOLDER)
+}
+
+/// Lowercased extension without the dot, rejecting anything that isn't a
+/// short alphanumeric suffix so it can't change the file's location
+fn normalize_extension(extension: &str) -> Result<String, String> {
+    let extension = extension.trim().trim_start_matches('.').to_lowercase();
+    if extension.is_empty()
+        || extension.len() > 10
+        || !extension.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err(format!("Invalid attachment extension: '{}'", extension));
+    }
+    Ok(extension)
+}
+
+/// `../` steps from the folder a note is in back up to the vault root,
+/// which is deeper for daily notes nested in dated folders
+fn path_to_vault_root(note_filename: &str) -> String {
+    let notes_dir = crate::get_notes_dir();
+    let depth = crate::find_note_file(note_filename)
+        .and_then(|(path, _)| {
+            path.parent()
+                .and_then(|dir| dir.strip_prefix(&notes_dir).ok())
+                .map(|dir| dir.components().count())
+        })
+        // Notes not saved yet will be at the top of `notes/` or `daily/`
+        .unwrap_or(1);
+
+    "../".repeat(depth)
+}
+
+/// Write pasted bytes to a new file in the assets folder, named after the
+/// note, and return a markdown image linking to it from the note
+pub fn save(note_filename: &str, data: &[u8], extension: &str) -> Result<String, String> {
+    let extension = normalize_extension(extension)?;
+    let assets_dir = get_assets_dir();
+    fs::create_dir_all(&assets_dir).map_err(|e| e.to_string())?;
+
+    let stem: String = note_filename
+        .trim_end_matches(".md")
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S%3f");
+
+    // Pastes in the same millisecond get a counter instead of overwriting
+    let mut counter = 0;
+    let (name, mut file) = loop {
+        let name = if counter == 0 {
+            format!("{}-{}.{}", stem, timestamp, extension)
+        } else {
+            format!("{}-{}-{}.{}", stem, timestamp, counter, extension)
+        };
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(assets_dir.join(&name))
+        {
+            Ok(file) => break (name, file),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => counter += 1,
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    file.write_all(data).map_err(|e| e.to_string())?;
+
+    Ok(format!(
+        "![]({}{}/{})",
+        path_to_vault_root(note_filename),
+        ASSETS_FOLDER,
+        name
+    ))
+}
+
+/// Files in the assets folder that none of `note_paths` mention by name
+pub fn orphans(note_paths: &[PathBuf]) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(get_assets_dir()) else {
+        return Vec::new();
+    };
+
+    let mut unreferenced: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+
+    for path in note_paths {
+        if unreferenced.is_empty() {
+            break;
+        }
+        if let Ok(content) = fs::read_to_string(path) {
+            // Editors may write the path with spaces escaped
+            unreferenced.retain(|name| {
+                !content.contains(name.as_str()) && !content.contains(&name.replace(' ', "%20"))
+            });
+        }
+    }
+
+    unreferenced.sort();
+    unreferenced
+}
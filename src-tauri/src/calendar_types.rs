@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+// Calendar data shared by the EventKit bridge (macOS) and the ICS reader
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum CalendarPermission {
+    NotDetermined,
+    Restricted,
+    Denied,
+    Authorized,
+    FullAccess,
+    /// No calendar to read on this platform, e.g. no `.ics` file configured
+    Unavailable,
+}
+
+impl From<i32> for CalendarPermission {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => CalendarPermission::NotDetermined,
+            1 => CalendarPermission::Restricted,
+            2 => CalendarPermission::Denied,
+            3 => CalendarPermission::Authorized,
+            4 => CalendarPermission::FullAccess,
+            _ => CalendarPermission::NotDetermined,
+        }
+    }
+}
+
+impl CalendarPermission {
+    pub fn is_authorized(self) -> bool {
+        matches!(
+            self,
+            CalendarPermission::Authorized | CalendarPermission::FullAccess
+        )
+    }
+}
+
+/// Tauri-managed permission status, checked once and kept until a request
+/// or refresh may have changed it
+pub struct CalendarPermissionState(pub Mutex<Option<CalendarPermission>>);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarInfo {
+    pub id: String,
+    pub title: String,
+    pub color: String,
+    #[serde(default)]
+    pub is_subscribed: bool,
+    #[serde(default)]
+    pub allows_modify: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    pub id: String,
+    pub title: String,
+    pub start: String,
+    pub end: String,
+    pub is_all_day: bool,
+    #[serde(default)]
+    pub location: String,
+    #[serde(default)]
+    pub notes: String,
+    pub calendar_id: String,
+    pub calendar_title: String,
+    pub calendar_color: String,
+    #[serde(default)]
+    pub url: String,
+}
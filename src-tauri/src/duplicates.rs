@@ -0,0 +1,92 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// Words per shingle; three keeps common phrases from matching unrelated notes
+const SHINGLE_WORDS: usize = 3;
+
+/// Hashes of every run of `SHINGLE_WORDS` consecutive words in a note's
+/// prose, compared case-insensitively and ignoring markdown syntax
+pub fn shingles(content: &str) -> HashSet<u64> {
+    let prose = crate::stats::strip_markdown(content).to_lowercase();
+    let words: Vec<&str> = prose
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    // Notes shorter than a shingle are compared as a whole
+    let size = SHINGLE_WORDS.min(words.len().max(1));
+    words
+        .windows(size)
+        .map(|window| {
+            let mut hasher = DefaultHasher::new();
+            window.hash(&mut hasher);
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// Jaccard similarity of two shingle sets, from 0 (nothing shared) to 1
+pub fn similarity(a: &HashSet<u64>, b: &HashSet<u64>) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        // Empty notes aren't duplicates of anything, including each other
+        return 0.0;
+    }
+    let shared = a.intersection(b).count();
+    shared as f32 / (a.len() + b.len() - shared) as f32
+}
+
+fn find_root(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[i] = root;
+    root
+}
+
+/// Group the notes whose shingle sets are at least `threshold` similar,
+/// joining pairs transitively. Each group lists note indices in order and
+/// the lowest similarity of the pairs that formed it.
+pub fn group(notes: &[HashSet<u64>], threshold: f32) -> Vec<(Vec<usize>, f32)> {
+    let mut parents: Vec<usize> = (0..notes.len()).collect();
+    let mut scores: Vec<Option<f32>> = vec![None; notes.len()];
+
+    for i in 0..notes.len() {
+        for j in (i + 1)..notes.len() {
+            let score = similarity(&notes[i], &notes[j]);
+            if score < threshold || score == 0.0 {
+                continue;
+            }
+
+            let (a, b) = (find_root(&mut parents, i), find_root(&mut parents, j));
+            if a == b {
+                continue;
+            }
+            let lowest = [scores[a], scores[b]]
+                .into_iter()
+                .flatten()
+                .fold(score, f32::min);
+            parents[b] = a;
+            scores[a] = Some(lowest);
+        }
+    }
+
+    let mut groups: Vec<(Vec<usize>, f32)> = Vec::new();
+    let mut group_of_root: Vec<Option<usize>> = vec![None; notes.len()];
+    for i in 0..notes.len() {
+        let root = find_root(&mut parents, i);
+        let Some(score) = scores[root] else {
+            continue;
+        };
+        match group_of_root[root] {
+            Some(g) => groups[g].0.push(i),
+            None => {
+                group_of_root[root] = Some(groups.len());
+                groups.push((vec![i], score));
+            }
+        }
+    }
+
+    groups
+}
@@ -0,0 +1,320 @@
+use pulldown_cmark::{html, Options, Parser};
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Heading {
+    pub level: usize,
+    pub text: String,
+    /// 1-based line in the note
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteBundle {
+    pub content: String,
+    /// Filenames of the notes included, in the order they appear
+    pub included: Vec<String>,
+}
+
+const EXPORT_STYLESHEET: &str = r#"
+body { margin: 0; background: #ffffff; color: #1f2937; }
+article { max-width: 720px; margin: 0 auto; padding: 48px 24px;
+  font: 16px/1.7 -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif; }
+h1, h2, h3 { line-height: 1.3; }
+a { color: #2563eb; }
+code { background: #f3f4f6; padding: 2px 4px; border-radius: 4px; }
+pre { background: #f3f4f6; padding: 12px 16px; border-radius: 6px; overflow-x: auto; }
+pre code { background: none; padding: 0; }
+blockquote { margin: 0; padding-left: 16px; border-left: 3px solid #d1d5db; color: #4b5563; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #d1d5db; padding: 6px 12px; }
+.wiki-link-missing { color: #9ca3af; text-decoration: underline dotted; }
+"#;
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Replace `[[wiki links]]` with anchors to the resolved note file, or a
+/// styled span when the target doesn't exist
+fn wiki_links_to_html(markdown: &str) -> String {
+    crate::WIKI_LINK_REGEX
+        .replace_all(markdown, |cap: &regex::Captures| {
+            let display = cap.get(1).map(|m| m.as_str().trim()).unwrap_or_default();
+            let raw_target = cap.get(2).map(|m| m.as_str()).unwrap_or(display);
+            let note_name = raw_target.split('#').next().unwrap_or(raw_target).trim();
+
+            match crate::note_exists(note_name) {
+                Ok((true, filename)) => format!(
+                    "<a class=\"wiki-link\" href=\"{}\">{}</a>",
+                    escape_html(&filename),
+                    escape_html(display)
+                ),
+                _ => format!(
+                    "<span class=\"wiki-link wiki-link-missing\">{}</span>",
+                    escape_html(display)
+                ),
+            }
+        })
+        .into_owned()
+}
+
+/// Render a note's markdown body to an HTML fragment
+pub fn render_markdown(markdown: &str) -> String {
+    let with_links = wiki_links_to_html(markdown);
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let parser = Parser::new_ext(&with_links, options);
+    let mut output = String::new();
+    html::push_html(&mut output, parser);
+    output
+}
+
+/// Render a note to a complete, self-contained HTML document
+pub fn render_document(title: &str, markdown: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<article>\n{}</article>\n</body>\n</html>\n",
+        escape_html(title),
+        EXPORT_STYLESHEET,
+        render_markdown(markdown)
+    )
+}
+
+/// Turn a heading into a markdown anchor, e.g. "Project Plan" -> "project-plan"
+fn heading_anchor(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                Some(c)
+            } else if c.is_whitespace() {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Anchor for a heading that doesn't collide with one already used, adding
+/// `-1`, `-2`, ... to repeated headings
+fn unique_anchor(text: &str, used: &mut HashSet<String>) -> String {
+    let base = heading_anchor(text);
+    let mut anchor = base.clone();
+    let mut counter = 1;
+    while !used.insert(anchor.clone()) {
+        anchor = format!("{}-{}", base, counter);
+        counter += 1;
+    }
+    anchor
+}
+
+/// Level and text of an ATX heading line such as `## Goals ##`
+pub fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    if text.is_empty() {
+        None
+    } else {
+        Some((level, text))
+    }
+}
+
+/// Level of a setext heading underline: `===` for 1, `---` for 2
+fn setext_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim();
+    // Indented four spaces it would be a code block
+    if trimmed.is_empty() || line.len() - line.trim_start().len() > 3 {
+        return None;
+    }
+    if trimmed.chars().all(|c| c == '=') {
+        Some(1)
+    } else if trimmed.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Every ATX (`## Goals`) and setext (a line underlined with `===` or
+/// `---`) heading in a note, skipping frontmatter and fenced code blocks.
+/// For setext headings only the line directly above the underline is used.
+pub fn outline(markdown: &str) -> Vec<Heading> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    // Frontmatter's closing `---` would otherwise underline its last field
+    let body_start = match lines.first() {
+        Some(first) if first.trim() == "---" => lines[1..]
+            .iter()
+            .position(|line| line.trim() == "---")
+            .map_or(0, |end| end + 2),
+        _ => 0,
+    };
+
+    let mut headings = Vec::new();
+    let mut in_code_fence = false;
+    // The previous line and its number, while it could be a setext heading's text
+    let mut paragraph: Option<(&str, usize)> = None;
+
+    for (i, line) in lines.iter().enumerate().skip(body_start) {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_fence = !in_code_fence;
+            paragraph = None;
+            continue;
+        }
+        if in_code_fence {
+            continue;
+        }
+
+        if let Some((level, text)) = parse_heading(line) {
+            headings.push(Heading {
+                level,
+                text: text.to_string(),
+                line: i + 1,
+            });
+            paragraph = None;
+            continue;
+        }
+
+        if let Some(level) = setext_level(line) {
+            // Without text above, `---` is a horizontal rule
+            if let Some((text, text_line)) = paragraph.take() {
+                headings.push(Heading {
+                    level,
+                    text: text.to_string(),
+                    line: text_line,
+                });
+            }
+            continue;
+        }
+
+        paragraph = if trimmed.is_empty() {
+            None
+        } else {
+            Some((line.trim(), i + 1))
+        };
+    }
+
+    headings
+}
+
+/// Build a nested list of links to every heading in a note, indented
+/// relative to its top-level heading. Headings in fenced code blocks are
+/// skipped.
+pub fn table_of_contents(markdown: &str) -> String {
+    let headings = outline(markdown);
+
+    let top_level = headings
+        .iter()
+        .map(|heading| heading.level)
+        .min()
+        .unwrap_or(1);
+    let mut used_anchors: HashSet<String> = HashSet::new();
+    let mut toc = String::new();
+
+    for heading in &headings {
+        let anchor = unique_anchor(&heading.text, &mut used_anchors);
+        toc.push_str(&format!(
+            "{}- [{}](#{})\n",
+            "  ".repeat(heading.level - top_level),
+            heading.text,
+            anchor
+        ));
+    }
+
+    toc
+}
+
+/// Collect a note and everything it links to (up to `depth` levels) into a
+/// single markdown document. Each note is included once, so link cycles and
+/// notes reachable by several paths are handled by the visited set.
+pub fn build_bundle(root: PathBuf, depth: u32) -> Result<NoteBundle, String> {
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut queue: VecDeque<(PathBuf, u32)> = VecDeque::new();
+    let mut sections: Vec<(String, String, String)> = Vec::new();
+
+    visited.insert(root.clone());
+    queue.push_back((root, 0));
+
+    while let Some((path, level)) = queue.pop_front() {
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let (_, body) = crate::frontmatter::parse_frontmatter(&content);
+
+        let filename = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        let heading = crate::first_heading(&body);
+        let title = crate::note_title(&filename, &content);
+
+        if level < depth {
+            for link in crate::parse_wiki_links(&body) {
+                let Ok((true, target)) = crate::note_exists(&link) else {
+                    continue;
+                };
+                if let Some((target_path, _)) = crate::find_note_file(&target) {
+                    if visited.insert(target_path.clone()) {
+                        queue.push_back((target_path, level + 1));
+                    }
+                }
+            }
+        }
+
+        // The section header replaces the note's own top-level heading
+        let mut heading_skipped = heading.is_none();
+        let body = body
+            .lines()
+            .filter(|line| {
+                if !heading_skipped && line.starts_with("# ") {
+                    heading_skipped = true;
+                    return false;
+                }
+                true
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        sections.push((filename, title, body.trim().to_string()));
+    }
+
+    let mut used_anchors: HashSet<String> = HashSet::new();
+    let mut toc = String::from("## Contents\n\n");
+    let mut combined = String::new();
+
+    for (_, title, body) in &sections {
+        let anchor = unique_anchor(title, &mut used_anchors);
+        toc.push_str(&format!("- [{}](#{})\n", title, anchor));
+        combined.push_str(&format!("\n## {}\n\n{}\n", title, body));
+    }
+
+    Ok(NoteBundle {
+        content: format!("{}{}", toc, combined),
+        included: sections
+            .into_iter()
+            .map(|(filename, _, _)| filename)
+            .collect(),
+    })
+}
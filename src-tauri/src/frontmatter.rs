@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Well-known frontmatter fields surfaced on `NoteFile`
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Frontmatter {
+    pub title: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub created: Option<String>,
+    /// Alternative names wiki links can use to reach the note
+    pub aliases: Option<Vec<String>>,
+    /// Date the note is about, for notes not named by date
+    pub date: Option<String>,
+}
+
+/// Split a note into its raw YAML frontmatter and body.
+/// Returns `None` when the note doesn't start with a `---` fence.
+pub fn split(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let yaml_start = content.len() - rest.len();
+
+    let mut offset = yaml_start;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed == "---" || trimmed == "..." {
+            let yaml = &content[yaml_start..offset];
+            let body = &content[offset + line.len()..];
+            return Some((yaml, body));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Parse the frontmatter of a note into a JSON-compatible property map
+pub fn read_properties(content: &str) -> Result<Map<String, Value>, String> {
+    let Some((yaml, _)) = split(content) else {
+        return Ok(Map::new());
+    };
+
+    if yaml.trim().is_empty() {
+        return Ok(Map::new());
+    }
+
+    match serde_yaml::from_str::<Value>(yaml) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(Value::Null) => Ok(Map::new()),
+        Ok(_) => Err("Frontmatter must be a key/value mapping".to_string()),
+        Err(e) => Err(format!("Invalid frontmatter: {}", e)),
+    }
+}
+
+/// Merge `props` into the note's frontmatter and return the new content.
+/// Keys set to null are removed, unspecified keys are preserved, and the
+/// body is kept byte-identical.
+pub fn merge_properties(content: &str, props: Map<String, Value>) -> Result<String, String> {
+    let mut merged = read_properties(content)?;
+    let body = split(content).map(|(_, body)| body).unwrap_or(content);
+
+    for (key, value) in props {
+        if value.is_null() {
+            merged.remove(&key);
+        } else {
+            merged.insert(key, value);
+        }
+    }
+
+    if merged.is_empty() {
+        return Ok(body.to_string());
+    }
+
+    let yaml = serde_yaml::to_string(&merged)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+
+    Ok(format!("---\n{}---\n{}", yaml, body))
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Read a list property that may be a YAML list or a comma-separated string
+fn string_list(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::Array(items) => Some(items.iter().filter_map(value_to_string).collect()),
+        Value::String(s) => Some(
+            s.split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Split the frontmatter from the body and extract the well-known fields.
+/// Notes without (valid) frontmatter yield `None` and their full content.
+pub fn parse_frontmatter(content: &str) -> (Option<Frontmatter>, String) {
+    let Some((_, body)) = split(content) else {
+        return (None, content.to_string());
+    };
+
+    let props = match read_properties(content) {
+        Ok(props) => props,
+        Err(e) => {
+            log::warn!("{}", e);
+            return (None, content.to_string());
+        }
+    };
+
+    let frontmatter = Frontmatter {
+        title: props.get("title").and_then(value_to_string),
+        tags: props.get("tags").and_then(string_list),
+        created: props.get("created").and_then(value_to_string),
+        aliases: props.get("aliases").and_then(string_list),
+        date: props.get("date").and_then(value_to_string),
+    };
+
+    (Some(frontmatter), body.to_string())
+}
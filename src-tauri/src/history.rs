@@ -0,0 +1,172 @@
+use chrono::{TimeZone, Utc};
+use git2::{IndexAddOption, Oid, Repository, Signature};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+const MAX_HISTORY: usize = 200;
+
+/// Kept out of version history: the trash and `.history/` snapshots are
+/// already recovery mechanisms, temp files only exist mid-write, and the undo
+/// log, pins and recents are app state that changes on every click rather
+/// than note content
+const DEFAULT_GITIGNORE: &str =
+    ".trash/\n/.history/\n.*.tmp\n/.undo.json\n/pinned.json\n/recents.json\n";
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Commit {
+    pub id: String,
+    pub message: String,
+    pub author: String,
+    /// Commit time, ISO-8601 UTC
+    pub time: String,
+}
+
+fn open_repo() -> Result<Repository, String> {
+    Repository::open(crate::get_notes_dir()).map_err(|e| e.to_string())
+}
+
+fn open_or_init_repo() -> Result<Repository, String> {
+    let notes_dir = crate::get_notes_dir();
+    if let Ok(repo) = Repository::open(&notes_dir) {
+        return Ok(repo);
+    }
+
+    let repo = Repository::init(&notes_dir).map_err(|e| e.to_string())?;
+    let gitignore = notes_dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, DEFAULT_GITIGNORE).map_err(|e| e.to_string())?;
+    }
+    Ok(repo)
+}
+
+/// The user's git identity if configured, otherwise a generic one so
+/// committing works on machines without git set up
+fn signature(repo: &Repository) -> Result<Signature<'static>, String> {
+    repo.signature()
+        .map(|sig| sig.to_owned())
+        .or_else(|_| Signature::now("Notomattic", "notomattic@localhost"))
+        .map_err(|e| e.to_string())
+}
+
+/// Stage every change in the vault (additions, edits and deletions) and
+/// commit it, creating the repository on first use. Returns the new commit
+/// id, or `None` when nothing changed since the last commit.
+pub fn commit_vault(message: Option<&str>) -> Result<Option<String>, String> {
+    let repo = open_or_init_repo()?;
+
+    let mut index = repo.index().map_err(|e| e.to_string())?;
+    index
+        .add_all(["*"].iter(), IndexAddOption::DEFAULT, None)
+        .map_err(|e| e.to_string())?;
+    index
+        .update_all(["*"].iter(), None)
+        .map_err(|e| e.to_string())?;
+    index.write().map_err(|e| e.to_string())?;
+
+    let tree_id = index.write_tree().map_err(|e| e.to_string())?;
+    let tree = repo.find_tree(tree_id).map_err(|e| e.to_string())?;
+
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    if parent.as_ref().is_some_and(|p| p.tree_id() == tree_id) {
+        return Ok(None);
+    }
+
+    let message = message
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            format!(
+                "Vault snapshot {}",
+                chrono::Local::now().format("%Y-%m-%d %H:%M")
+            )
+        });
+
+    let sig = signature(&repo)?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let id = repo
+        .commit(Some("HEAD"), &sig, &sig, &message, &tree, &parents)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(id.to_string()))
+}
+
+/// Blob id of the file at `path` in a commit, if it existed there
+fn blob_at(commit: &git2::Commit, path: &Path) -> Option<Oid> {
+    commit
+        .tree()
+        .ok()?
+        .get_path(path)
+        .ok()
+        .map(|entry| entry.id())
+}
+
+fn to_commit_info(commit: &git2::Commit) -> Commit {
+    let time = Utc
+        .timestamp_opt(commit.time().seconds(), 0)
+        .single()
+        .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default();
+
+    Commit {
+        id: commit.id().to_string(),
+        message: commit.message().unwrap_or("").trim_end().to_string(),
+        author: commit.author().name().unwrap_or("").to_string(),
+        time,
+    }
+}
+
+/// Commits that changed the note at the vault-relative `path`, newest first
+pub fn note_history(path: &str) -> Result<Vec<Commit>, String> {
+    let Ok(repo) = open_repo() else {
+        // No repository yet means no history, not an error
+        return Ok(Vec::new());
+    };
+    let path = Path::new(path);
+
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    if revwalk.push_head().is_err() {
+        return Ok(Vec::new());
+    }
+
+    let mut history = Vec::new();
+    for oid in revwalk.flatten() {
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+
+        let current = blob_at(&commit, path);
+        let previous = commit
+            .parent(0)
+            .ok()
+            .and_then(|parent| blob_at(&parent, path));
+        if current != previous {
+            history.push(to_commit_info(&commit));
+            if history.len() >= MAX_HISTORY {
+                break;
+            }
+        }
+    }
+
+    Ok(history)
+}
+
+/// Content of the first of `paths` that exists in `commit` (a full or
+/// abbreviated commit id)
+pub fn note_at(paths: &[String], commit: &str) -> Result<String, String> {
+    let repo = open_repo().map_err(|_| "The vault has no version history yet".to_string())?;
+    let commit = repo
+        .revparse_single(commit)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| format!("Unknown commit '{}'", commit))?;
+
+    let oid = paths
+        .iter()
+        .find_map(|path| blob_at(&commit, Path::new(path)))
+        .ok_or("The note did not exist in that commit")?;
+    let blob = repo.find_blob(oid).map_err(|e| e.to_string())?;
+
+    Ok(String::from_utf8_lossy(blob.content()).to_string())
+}
@@ -0,0 +1,602 @@
+use chrono::{
+    Datelike, Days, Local, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc, Weekday,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+pub use crate::calendar_types::{
+    CalendarEvent, CalendarInfo, CalendarPermission, CalendarPermissionState,
+};
+
+// Calendar support for platforms without EventKit: events are read from a
+// user-configured `.ics` file and exposed through the same API as the
+// EventKit bridge.
+
+const DEFAULT_CALENDAR_COLOR: &str = "#3B82F6";
+
+/// Guard against ranges so long they'd otherwise return endless occurrences
+const MAX_OCCURRENCES: usize = 5000;
+
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Default)]
+struct RawEvent {
+    uid: String,
+    summary: String,
+    location: String,
+    description: String,
+    url: String,
+    start: Option<(NaiveDateTime, bool)>,
+    end: Option<NaiveDateTime>,
+    duration: Option<chrono::Duration>,
+    rrule: Option<String>,
+    exdates: HashSet<NaiveDateTime>,
+}
+
+enum Frequency {
+    Daily,
+    Weekly,
+    /// Rules we don't expand; only the first occurrence is shown
+    Unsupported,
+}
+
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u64,
+    count: Option<usize>,
+    until: Option<NaiveDateTime>,
+    by_day: Vec<Weekday>,
+}
+
+struct ParsedCalendar {
+    title: Option<String>,
+    color: Option<String>,
+    events: Vec<RawEvent>,
+}
+
+/// Join folded continuation lines (those starting with a space or tab)
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.lines() {
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first colon outside a quoted parameter
+    let mut in_quotes = false;
+    let split_at = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..split_at], &line[split_at + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim_matches('"').to_string()))
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Parse a DATE or DATE-TIME value into local time, reporting whether it was
+/// a date (all-day). UTC values are converted; times with a TZID or no zone
+/// are taken as local time.
+fn parse_moment(value: &str, is_date: bool) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+
+    if is_date || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_hms_opt(0, 0, 0)?, true));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        let local = Utc.from_utc_datetime(&naive).with_timezone(&Local);
+        return Some((local.naive_local(), false));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Some((naive, false))
+}
+
+fn parse_property_moment(property: &Property) -> Option<(NaiveDateTime, bool)> {
+    let is_date = property
+        .param("VALUE")
+        .is_some_and(|v| v.eq_ignore_ascii_case("DATE"));
+    parse_moment(&property.value, is_date)
+}
+
+/// Parse a basic duration such as `PT1H30M` or `P1D`
+fn parse_duration(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut rest = value.strip_prefix('P')?;
+
+    let mut seconds: i64 = 0;
+    let mut in_time = false;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('T') {
+            in_time = true;
+            rest = after;
+            continue;
+        }
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let amount: i64 = rest[..digits].parse().ok()?;
+        let unit = rest[digits..].chars().next()?;
+        seconds += amount
+            * match (unit, in_time) {
+                ('W', false) => 7 * 24 * 3600,
+                ('D', false) => 24 * 3600,
+                ('H', true) => 3600,
+                ('M', true) => 60,
+                ('S', true) => 1,
+                _ => return None,
+            };
+        rest = &rest[digits + 1..];
+    }
+
+    Some(chrono::Duration::seconds(if negative {
+        -seconds
+    } else {
+        seconds
+    }))
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    // Ordinal prefixes like "2MO" only apply to monthly rules, so ignore them
+    let code = code.trim_start_matches(|c: char| c.is_ascii_digit() || c == '+' || c == '-');
+    match code.to_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_rrule(value: &str) -> RecurrenceRule {
+    let mut rule = RecurrenceRule {
+        frequency: Frequency::Unsupported,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+    };
+
+    for part in value.split(';') {
+        let Some((key, val)) = part.split_once('=') else {
+            continue;
+        };
+        match key.to_uppercase().as_str() {
+            "FREQ" => {
+                rule.frequency = match val.to_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    _ => Frequency::Unsupported,
+                }
+            }
+            "INTERVAL" => rule.interval = val.parse().unwrap_or(1).max(1),
+            "COUNT" => rule.count = val.parse().ok(),
+            "UNTIL" => rule.until = parse_moment(val, false).map(|(moment, _)| moment),
+            "BYDAY" => rule.by_day = val.split(',').filter_map(parse_weekday).collect(),
+            _ => {}
+        }
+    }
+
+    rule
+}
+
+fn parse_calendar(content: &str) -> ParsedCalendar {
+    let mut calendar = ParsedCalendar {
+        title: None,
+        color: None,
+        events: Vec::new(),
+    };
+    let mut current: Option<RawEvent> = None;
+    // Nested components (e.g. VALARM) inside an event are skipped
+    let mut nested_depth = 0;
+
+    for line in unfold(content) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        let value = property.value.trim();
+
+        match (property.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                current = Some(RawEvent::default());
+            }
+            ("BEGIN", Some(_)) => nested_depth += 1,
+            ("END", Some(_)) if nested_depth > 0 => nested_depth -= 1,
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(event) = current.take() {
+                    calendar.events.push(event);
+                }
+            }
+            (_, Some(_)) if nested_depth > 0 => {}
+            ("X-WR-CALNAME", None) => calendar.title = Some(unescape(value)),
+            ("X-APPLE-CALENDAR-COLOR", None) => {
+                calendar.color = value
+                    .get(..7)
+                    .filter(|c| c.starts_with('#'))
+                    .map(str::to_uppercase)
+            }
+            ("UID", Some(event)) => event.uid = value.to_string(),
+            ("SUMMARY", Some(event)) => event.summary = unescape(value),
+            ("LOCATION", Some(event)) => event.location = unescape(value),
+            ("DESCRIPTION", Some(event)) => event.description = unescape(value),
+            ("URL", Some(event)) => event.url = value.to_string(),
+            ("DTSTART", Some(event)) => event.start = parse_property_moment(&property),
+            ("DTEND", Some(event)) => {
+                event.end = parse_property_moment(&property).map(|(moment, _)| moment)
+            }
+            ("DURATION", Some(event)) => event.duration = parse_duration(value),
+            ("RRULE", Some(event)) => event.rrule = Some(value.to_string()),
+            ("EXDATE", Some(event)) => {
+                let is_date = property
+                    .param("VALUE")
+                    .is_some_and(|v| v.eq_ignore_ascii_case("DATE"));
+                event.exdates.extend(
+                    value
+                        .split(',')
+                        .filter_map(|v| parse_moment(v, is_date))
+                        .map(|(moment, _)| moment),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    calendar
+}
+
+/// Start times of an event's occurrences from `from` to `range_end`.
+/// Occurrences before `from` are skipped without being generated, though
+/// COUNT still counts them from the series start.
+fn occurrence_starts(
+    start: NaiveDateTime,
+    rule: Option<&RecurrenceRule>,
+    from: NaiveDateTime,
+    range_end: NaiveDateTime,
+) -> Vec<NaiveDateTime> {
+    let Some(rule) = rule else {
+        return vec![start];
+    };
+
+    // `index` is the occurrence's position in the whole series
+    let within_rule = |moment: NaiveDateTime, index: usize| {
+        !matches!(rule.count, Some(count) if index >= count)
+            && !matches!(rule.until, Some(until) if moment > until)
+            && moment <= range_end
+    };
+    // Whole periods of `days` days between `start` and `from`, rounded down
+    let periods_before_from = |origin: NaiveDateTime, days: u64| {
+        let seconds = (from - origin).num_seconds().max(0) as u64;
+        seconds / (days * 24 * 60 * 60).max(1)
+    };
+
+    let mut starts = Vec::new();
+    match rule.frequency {
+        Frequency::Unsupported => starts.push(start),
+        Frequency::Daily => {
+            let mut step = periods_before_from(start, rule.interval);
+            while let Some(moment) = start.checked_add_days(Days::new(step * rule.interval)) {
+                if !within_rule(moment, step as usize) || starts.len() >= MAX_OCCURRENCES {
+                    break;
+                }
+                if moment >= from {
+                    starts.push(moment);
+                }
+                step += 1;
+            }
+        }
+        Frequency::Weekly => {
+            let mut weekdays = if rule.by_day.is_empty() {
+                vec![start.weekday()]
+            } else {
+                rule.by_day.clone()
+            };
+            weekdays.sort_by_key(|day| day.num_days_from_monday());
+            weekdays.dedup();
+
+            let offset = u64::from(start.weekday().num_days_from_monday());
+            let Some(week_start) = start.checked_sub_days(Days::new(offset)) else {
+                return starts;
+            };
+            // Days of the first week before DTSTART aren't occurrences
+            let first_week = weekdays
+                .iter()
+                .filter(|day| u64::from(day.num_days_from_monday()) >= offset)
+                .count();
+
+            let mut week = periods_before_from(week_start, 7 * rule.interval);
+            'weeks: loop {
+                let mut index = if week == 0 {
+                    0
+                } else {
+                    first_week + (week as usize - 1) * weekdays.len()
+                };
+                for day in &weekdays {
+                    let days = week * 7 * rule.interval + u64::from(day.num_days_from_monday());
+                    let Some(moment) = week_start.checked_add_days(Days::new(days)) else {
+                        break 'weeks;
+                    };
+                    if moment < start {
+                        continue;
+                    }
+                    if !within_rule(moment, index) || starts.len() >= MAX_OCCURRENCES {
+                        break 'weeks;
+                    }
+                    if moment >= from {
+                        starts.push(moment);
+                    }
+                    index += 1;
+                }
+                week += 1;
+            }
+        }
+    }
+
+    starts
+}
+
+fn to_iso(moment: NaiveDateTime) -> String {
+    Local
+        .from_local_datetime(&moment)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&moment))
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn read_calendar(path: &Path) -> Result<ParsedCalendar, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read calendar file: {}", e))?;
+    Ok(parse_calendar(&content))
+}
+
+fn calendar_info(path: &Path, calendar: &ParsedCalendar) -> CalendarInfo {
+    CalendarInfo {
+        id: path.to_string_lossy().to_string(),
+        title: calendar.title.clone().unwrap_or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Calendar".to_string())
+        }),
+        color: calendar
+            .color
+            .clone()
+            .unwrap_or_else(|| DEFAULT_CALENDAR_COLOR.to_string()),
+        is_subscribed: true,
+        allows_modify: false,
+    }
+}
+
+/// A configured, readable calendar file counts as authorized
+pub fn get_permission_status() -> CalendarPermission {
+    match crate::settings::ics_path() {
+        Some(path) if path.is_file() => CalendarPermission::Authorized,
+        Some(_) => CalendarPermission::Denied,
+        None => CalendarPermission::Unavailable,
+    }
+}
+
+/// There's no system prompt for a file; access depends on the configured path
+pub fn request_permission() -> bool {
+    is_authorized()
+}
+
+pub fn is_authorized() -> bool {
+    get_permission_status() == CalendarPermission::Authorized
+}
+
+/// The configured file is exposed as a single calendar; without one there
+/// are no calendars
+pub fn get_calendars() -> Result<Vec<CalendarInfo>, String> {
+    let Some(path) = crate::settings::ics_path() else {
+        return Ok(Vec::new());
+    };
+    let calendar = read_calendar(&path)?;
+    Ok(vec![calendar_info(&path, &calendar)])
+}
+
+/// Fetch events overlapping a date range (YYYY-MM-DD, inclusive), none
+/// when no file is configured
+pub fn get_events(
+    start_date: &str,
+    end_date: &str,
+    calendar_id: Option<&str>,
+) -> Result<Vec<CalendarEvent>, String> {
+    let parse_day = |date: &str| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| "Invalid date format".to_string())
+    };
+    let range_start = parse_day(start_date)?
+        .and_hms_opt(0, 0, 0)
+        .ok_or("Invalid start date")?;
+    let range_end = parse_day(end_date)?
+        .and_hms_opt(23, 59, 59)
+        .ok_or("Invalid end date")?;
+
+    let Some(path) = crate::settings::ics_path() else {
+        return Ok(Vec::new());
+    };
+    let calendar = read_calendar(&path)?;
+    let info = calendar_info(&path, &calendar);
+
+    if calendar_id.is_some_and(|id| !id.is_empty() && id != info.id) {
+        return Ok(Vec::new());
+    }
+
+    let mut events = Vec::new();
+    for event in &calendar.events {
+        let Some((start, is_all_day)) = event.start else {
+            continue;
+        };
+        let length = match (event.end, event.duration) {
+            (Some(end), _) => end - start,
+            (None, Some(duration)) => duration,
+            (None, None) if is_all_day => chrono::Duration::days(1),
+            (None, None) => chrono::Duration::zero(),
+        };
+        let rule = event.rrule.as_deref().map(parse_rrule);
+        let recurring = rule
+            .as_ref()
+            .is_some_and(|r| !matches!(r.frequency, Frequency::Unsupported));
+
+        // Occurrences starting before the range may still run into it
+        let from = range_start
+            .checked_sub_signed(length)
+            .unwrap_or(range_start);
+        for occurrence in occurrence_starts(start, rule.as_ref(), from, range_end) {
+            let occurrence_end = occurrence + length;
+            let overlaps = occurrence <= range_end
+                && (occurrence_end > range_start || occurrence >= range_start);
+            if !overlaps || event.exdates.contains(&occurrence) {
+                continue;
+            }
+
+            let id = if recurring {
+                format!("{}-{}", event.uid, occurrence.format("%Y%m%dT%H%M%S"))
+            } else {
+                event.uid.clone()
+            };
+
+            events.push(CalendarEvent {
+                id,
+                title: if event.summary.is_empty() {
+                    "(No title)".to_string()
+                } else {
+                    event.summary.clone()
+                },
+                start: to_iso(occurrence),
+                end: to_iso(occurrence_end),
+                is_all_day,
+                location: event.location.clone(),
+                notes: event.description.clone(),
+                calendar_id: info.id.clone(),
+                calendar_title: info.title.clone(),
+                calendar_color: info.color.clone(),
+                url: event.url.clone(),
+            });
+        }
+    }
+
+    // Sort by start time, like the EventKit bridge
+    events.sort_by(|a, b| a.start.cmp(&b.start));
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moment(date: &str) -> NaiveDateTime {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+    }
+
+    fn day_range(date: &str) -> (NaiveDateTime, NaiveDateTime) {
+        let day = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        (
+            day.and_hms_opt(0, 0, 0).unwrap(),
+            day.and_hms_opt(23, 59, 59).unwrap(),
+        )
+    }
+
+    #[test]
+    fn long_running_daily_series_reaches_the_range() {
+        let rule = parse_rrule("FREQ=DAILY");
+        let (from, to) = day_range("2026-10-14");
+        let starts = occurrence_starts(moment("2000-01-03"), Some(&rule), from, to);
+        assert_eq!(starts, vec![moment("2026-10-14")]);
+    }
+
+    #[test]
+    fn count_is_counted_from_the_series_start() {
+        let rule = parse_rrule("FREQ=DAILY;COUNT=5");
+        let (from, _) = day_range("2024-01-03");
+        let (_, to) = day_range("2024-01-31");
+        let starts = occurrence_starts(moment("2024-01-01"), Some(&rule), from, to);
+        assert_eq!(
+            starts,
+            vec![
+                moment("2024-01-03"),
+                moment("2024-01-04"),
+                moment("2024-01-05")
+            ]
+        );
+    }
+
+    #[test]
+    fn weekly_count_spans_the_skipped_weeks() {
+        // Wednesday start, so the first week only has the Wednesday
+        let rule = parse_rrule("FREQ=WEEKLY;BYDAY=MO,WE;COUNT=6");
+        let (from, _) = day_range("2024-01-15");
+        let (_, to) = day_range("2024-12-31");
+        let starts = occurrence_starts(moment("2024-01-03"), Some(&rule), from, to);
+        // 01-03, then 01-08 and 01-10 are skipped; 01-15 is the fourth
+        assert_eq!(
+            starts,
+            vec![
+                moment("2024-01-15"),
+                moment("2024-01-17"),
+                moment("2024-01-22")
+            ]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedImport {
+    /// Filename in the source folder
+    pub source: String,
+    /// Filename the note was imported as
+    pub filename: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    /// Notes imported under their slugified name
+    pub imported: Vec<String>,
+    /// Source files that were not imported
+    pub skipped: Vec<String>,
+    /// Notes imported under a different name to avoid a collision
+    pub renamed: Vec<RenamedImport>,
+}
+
+/// Daily notes are keyed by date, so their names can't be slugified or
+/// suffixed without breaking the calendar
+fn daily_filename(stem: &str) -> Option<String> {
+    chrono::NaiveDate::parse_from_str(stem.trim(), "%Y-%m-%d")
+        .ok()
+        .map(|date| format!("{}.md", date.format("%Y-%m-%d")))
+}
+
+/// Copy the `.md` files at the top level of `source_dir` into the daily or
+/// standalone folder. Files are copied byte-for-byte, frontmatter included.
+pub fn import_folder(source_dir: &Path, as_daily: bool) -> Result<ImportReport, String> {
+    if !source_dir.is_dir() {
+        return Err(format!("'{}' is not a folder", source_dir.display()));
+    }
+
+    let target_dir = if as_daily {
+        crate::get_daily_dir()
+    } else {
+        crate::get_standalone_dir()
+    };
+    fs::create_dir_all(&target_dir).map_err(|e| e.to_string())?;
+
+    let mut sources: Vec<_> = fs::read_dir(source_dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    sources.sort();
+
+    let mut report = ImportReport {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        renamed: Vec::new(),
+    };
+
+    for path in sources {
+        let source = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let filename = if as_daily {
+            daily_filename(&stem)
+        } else {
+            Some(crate::note_name_to_filename(&stem)).filter(|name| name != ".md")
+        };
+        let Some(filename) = filename else {
+            report.skipped.push(source);
+            continue;
+        };
+
+        // Daily notes may be nested in dated folders
+        let note_dir = if as_daily {
+            crate::get_daily_note_dir(&filename)
+        } else {
+            target_dir.clone()
+        };
+        fs::create_dir_all(&note_dir).map_err(|e| e.to_string())?;
+
+        let contents = fs::read(&path).map_err(|e| e.to_string())?;
+        let base = filename.trim_end_matches(".md").to_string();
+        let mut final_name = filename.clone();
+        let mut destination = note_dir.join(&final_name);
+        let mut counter = 1;
+        let mut already_imported = false;
+
+        while destination.exists() {
+            // Re-importing the same file is a no-op rather than a duplicate
+            if fs::read(&destination).ok().as_ref() == Some(&contents) || as_daily {
+                already_imported = true;
+                break;
+            }
+            final_name = format!("{}-{}.md", base, counter);
+            destination = note_dir.join(&final_name);
+            counter += 1;
+        }
+
+        if already_imported {
+            report.skipped.push(source);
+            continue;
+        }
+
+        fs::copy(&path, &destination).map_err(|e| e.to_string())?;
+
+        if final_name == filename {
+            report.imported.push(final_name);
+        } else {
+            report.renamed.push(RenamedImport {
+                source,
+                filename: final_name,
+            });
+        }
+    }
+
+    Ok(report)
+}
@@ -1,22 +1,175 @@
 use chrono::Local;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use lazy_static::lazy_static;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_shell::ShellExt;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+mod aliases;
+mod archive;
+mod attachments;
+mod duplicates;
+mod export;
+mod frontmatter;
+mod history;
+mod import;
+mod link_index;
+mod pdf;
+mod periodic;
+mod pins;
+mod recents;
+mod search;
+mod settings;
+mod snapshots;
+mod stats;
+mod tags;
+mod trash;
+mod undo;
+mod watcher;
+use export::{Heading, NoteBundle};
+use frontmatter::Frontmatter;
+use history::Commit;
+use import::ImportReport;
+use link_index::{LinkCache, LinkIndexState};
+use periodic::Period;
+use settings::NamingStrategy;
+use snapshots::NoteVersion;
+use stats::{ContentStats, NoteStats, VaultStats, WordCountOptions};
+use trash::TrashEntry;
+use undo::{RelinkedNote, UndoAction, UndoResult};
 
 #[cfg(target_os = "macos")]
 mod calendar;
-#[cfg(target_os = "macos")]
-use calendar::{CalendarEvent, CalendarInfo, CalendarPermission};
+mod calendar_types;
+// Elsewhere, calendars come from a user-configured .ics file with the same API
+#[cfg(not(target_os = "macos"))]
+#[path = "ics.rs"]
+mod calendar;
+use calendar::{CalendarEvent, CalendarInfo, CalendarPermission, CalendarPermissionState};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct NoteFile {
     name: String,
     path: String,
     is_daily: bool,
     date: Option<String>,
+    title: Option<String>,
+    tags: Option<Vec<String>>,
+    is_pinned: bool,
+    /// Start of the note's body text, only filled in when requested
+    preview: Option<String>,
+    /// Last modification time (ISO-8601, UTC)
+    modified: Option<String>,
+    /// File creation time, where the OS and filesystem record one
+    created: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+enum SortKey {
+    Name,
+    Modified,
+    Created,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NotePage {
+    notes: Vec<NoteFile>,
+    /// Total number of notes before pagination
+    total: usize,
+}
+
+/// Which way `adjacent_daily_note` looks from a date
+#[derive(Debug, Deserialize, Clone, Copy)]
+enum Direction {
+    Previous,
+    Next,
+}
+
+/// Which notes `clear_all_notes` removes
+#[derive(Debug, Deserialize, Clone, Copy)]
+enum ClearScope {
+    All,
+    Daily,
+    Standalone,
+}
+
+/// Identifies a note for commands that act on several at once
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct NoteRef {
+    filename: String,
+    is_daily: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DeleteOutcome {
+    filename: String,
+    is_daily: bool,
+    /// Why the note couldn't be deleted; `None` when it was
+    error: Option<String>,
+}
+
+/// Notes whose links would break if a note were deleted
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DeleteImpact {
+    count: usize,
+    linking_notes: Vec<NoteRef>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DeleteReport {
+    /// One entry per requested note, in request order
+    results: Vec<DeleteOutcome>,
+    deleted: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct RelinkReport {
+    /// Notes whose links now point at the new name
+    updated: usize,
+    /// Notes that link to the old name but couldn't be rewritten
+    failed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct StatsUpdatedEvent {
+    filename: String,
+    is_daily: bool,
+    word_count: usize,
+    char_count: usize,
+    line_count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ScanProgressEvent {
+    /// Which command is scanning, e.g. "find_broken_links"
+    scan: String,
+    processed: usize,
+    total: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct TagCount {
+    tag: String,
+    count: usize,
 }
 
 // Template System Data Structures
@@ -30,6 +183,12 @@ struct Template {
     icon: String,
     is_default: bool,
     content: String,
+    /// Group the template is listed under; uncategorized when unset
+    category: Option<String>,
+    /// A fragment inserted at the cursor with `render_snippet`, rather than
+    /// a scaffold for new notes
+    #[serde(default)]
+    is_snippet: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -41,22 +200,81 @@ struct TemplateFile {
     icon: String,
 }
 
+/// Several template files on disk producing the same id
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct TemplateConflict {
+    id: String,
+    /// Files in the templates folder with this id, the one in use first
+    files: Vec<String>,
+    /// The id belongs to a default template, which takes precedence
+    shadows_default: bool,
+}
+
+/// File format for moving custom templates between vaults
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateBundle {
+    templates: Vec<Template>,
+}
+
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct SaveTemplateInput {
     name: String,
     description: String,
     icon: String,
     content: String,
+    category: Option<String>,
+    #[serde(default)]
+    is_snippet: bool,
+    /// Skip the unknown-variable check for templates using custom variables
+    #[serde(default)]
+    allow_custom_variables: bool,
 }
 
 // Wiki Link System Data Structures
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct NoteExistence {
+    /// Resolved filename, or the one a new note with the name would get
+    filename: String,
+    exists: bool,
+    is_daily: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DayCount {
+    /// YYYY-MM-DD
+    date: String,
+    word_count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct VersionedNote {
+    content: String,
+    /// Modification time in milliseconds, to pass back to `write_note` as
+    /// `expected_mtime`; None while the note doesn't exist yet
+    mtime: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct WikiLink {
+    /// The note name the link points at
     text: String,
+    /// The text shown for the link: the part before `|`, or the link as written
+    display: String,
     target: String,
     exists: bool,
+    heading: Option<String>,
+    /// Whether `heading` appears in the target note (None when no heading)
+    heading_found: Option<bool>,
+    /// For a link that doesn't resolve, the filename of the closest existing note
+    suggestion: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -65,6 +283,71 @@ struct BacklinkInfo {
     from_note: String,
     from_title: String,
     context: String,
+    /// Nearest heading above the link in the source note
+    section: Option<String>,
+    /// The note was over the size limit, so it wasn't read for context
+    too_large: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DuplicateGroup {
+    notes: Vec<NoteRef>,
+    /// Lowest Jaccard similarity (0-1) between the pairs that put notes in the group
+    similarity: f32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct BrokenLink {
+    from_note: String,
+    is_daily: bool,
+    /// The link as written, without any `#heading` suffix
+    link: String,
+    context: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GraphNode {
+    /// Vault-relative path, e.g. `notes/my-note.md`
+    id: String,
+    title: String,
+    is_daily: bool,
+    /// False for dangling nodes created from unresolved links
+    exists: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GraphEdge {
+    from: String,
+    to: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct LinkGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct FileReplacement {
+    filename: String,
+    is_daily: bool,
+    count: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ReplaceReport {
+    /// Notes with at least one match
+    files: Vec<FileReplacement>,
+    total: usize,
+    /// True when nothing was written
+    dry_run: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -78,12 +361,26 @@ struct LinkIndex {
 lazy_static! {
     // Matches [[Note Name]] or [[Display|note-name]]
     static ref WIKI_LINK_REGEX: Regex = Regex::new(r"\[\[([^\]|]+)(?:\|([^\]]+))?\]\]").unwrap();
+    // Matches ![[Note Name]] embeds, with the same target syntax as links
+    static ref EMBED_REGEX: Regex = Regex::new(r"!\[\[([^\]|]+)(?:\|([^\]]+))?\]\]").unwrap();
+    // Matches [Display](path/to/note.md), with an optional #heading
+    static ref MARKDOWN_NOTE_LINK_REGEX: Regex =
+        Regex::new(r"\[[^\]]*\]\(<?([^()<>\s]+\.md)>?(?:#[^()\s]*)?\)").unwrap();
 }
 
+// Template Variable Regex
+lazy_static! {
+    // Matches {{variable}} placeholders, allowing inner whitespace
+    static ref TEMPLATE_VARIABLE_REGEX: Regex = Regex::new(r"\{\{\s*([A-Za-z0-9_]+)\s*\}\}").unwrap();
+}
+
+/// The vault root: the path chosen in settings, or `Documents/Notomattic`
 fn get_notes_dir() -> PathBuf {
-    dirs::document_dir()
-        .expect("Could not find Documents directory")
-        .join("Notomattic")
+    settings::vault_path().unwrap_or_else(|| {
+        dirs::document_dir()
+            .expect("Could not find Documents directory")
+            .join("Notomattic")
+    })
 }
 
 fn get_daily_dir() -> PathBuf {
@@ -94,14 +391,190 @@ fn get_standalone_dir() -> PathBuf {
     get_notes_dir().join("notes")
 }
 
+/// Every `.md` file in the daily or standalone folder. Daily notes may be
+/// nested in dated subfolders (see `get_daily_note_dir`), so that folder is
+/// walked recursively; hidden folders are skipped.
+fn list_note_paths(is_daily: bool) -> Vec<PathBuf> {
+    list_note_paths_with(is_daily, &[])
+}
+
+/// `list_note_paths`, also listing files with any of `extra_extensions`
+/// (lowercase, without the dot), e.g. `txt` or `canvas`
+fn list_note_paths_with(is_daily: bool, extra_extensions: &[String]) -> Vec<PathBuf> {
+    fn walk(dir: &Path, recursive: bool, extensions: &[String], paths: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if recursive && !hidden {
+                    walk(&path, recursive, extensions, paths);
+                }
+            } else if path.extension().is_some_and(|ext| {
+                let ext = ext.to_string_lossy().to_lowercase();
+                ext == "md" || extensions.contains(&ext)
+            }) {
+                paths.push(path);
+            }
+        }
+    }
+
+    let mut paths = Vec::new();
+    if is_daily {
+        walk(&get_daily_dir(), true, extra_extensions, &mut paths);
+    } else {
+        walk(&get_standalone_dir(), false, extra_extensions, &mut paths);
+    }
+    paths
+}
+
+/// Folder a daily note lives in. With a daily folder format such as `%Y/%m`,
+/// `2025-01-31.md` goes in `daily/2025/01/`. Notes already stored elsewhere in
+/// the daily folder (written before the format was set or changed) are found
+/// where they are rather than moved; the tree is only walked when a format is
+/// set and the note isn't at either place. Names that aren't dates stay at
+/// the top.
+fn get_daily_note_dir(filename: &str) -> PathBuf {
+    let daily_dir = get_daily_dir();
+    let Some(date) = filename
+        .strip_suffix(".md")
+        .and_then(|stem| chrono::NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok())
+    else {
+        return daily_dir;
+    };
+
+    // Without a folder format every daily note sits directly in the folder
+    let Some(format) = settings::daily_folder_format() else {
+        return daily_dir;
+    };
+    let formatted = daily_dir.join(date.format(&format).to_string());
+    if formatted.join(filename).exists() {
+        return formatted;
+    }
+    if daily_dir.join(filename).exists() {
+        return daily_dir;
+    }
+
+    // Notes filed under an earlier format are only found by walking the tree
+    list_note_paths(true)
+        .into_iter()
+        .find(|path| path.file_name() == Some(std::ffi::OsStr::new(filename)))
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .unwrap_or(formatted)
+}
+
+/// Date a note belongs to on the calendar: a daily note's filename when it's
+/// a date, otherwise the `date` frontmatter field as `YYYY-MM-DD`. Daily
+/// notes with neither keep their filename stem, as before.
+fn note_date(name: &str, is_daily: bool, frontmatter: &Frontmatter) -> Option<String> {
+    let parse = |text: &str| {
+        // Frontmatter dates may carry a time, e.g. `2025-01-31T09:00`
+        let text = text.trim();
+        chrono::NaiveDate::parse_from_str(text.get(..10).unwrap_or(text), "%Y-%m-%d")
+            .ok()
+            .map(|date| date.format("%Y-%m-%d").to_string())
+    };
+    let stem = name.strip_suffix(".md").unwrap_or(name);
+
+    if is_daily {
+        if let Some(date) = parse(stem) {
+            return Some(date);
+        }
+    }
+    if let Some(date) = frontmatter.date.as_deref().and_then(parse) {
+        return Some(date);
+    }
+    is_daily.then(|| stem.to_string())
+}
+
+fn read_frontmatter(path: &Path) -> Frontmatter {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| frontmatter::parse_frontmatter(&content).0)
+        .unwrap_or_default()
+}
+
+// Vault Ignore Helper Functions
+
+const IGNORE_FILENAME: &str = ".notomatticignore";
+
+/// Loads the gitignore-style rules from `.notomatticignore` at the vault root.
+/// The file is re-read on every scan so edits apply without a restart.
+fn load_ignore_rules() -> Gitignore {
+    load_ignore_rules_in(&get_notes_dir())
+}
+
+fn load_ignore_rules_in(notes_dir: &Path) -> Gitignore {
+    let ignore_path = notes_dir.join(IGNORE_FILENAME);
+
+    if !ignore_path.exists() {
+        return Gitignore::empty();
+    }
+
+    let mut builder = GitignoreBuilder::new(notes_dir);
+    if let Some(e) = builder.add(&ignore_path) {
+        log::warn!("Failed to parse {}: {}", IGNORE_FILENAME, e);
+    }
+
+    builder.build().unwrap_or_else(|e| {
+        log::warn!("Failed to build ignore rules: {}", e);
+        Gitignore::empty()
+    })
+}
+
+fn is_ignored(rules: &Gitignore, path: &Path) -> bool {
+    rules
+        .matched_path_or_any_parents(path, path.is_dir())
+        .is_ignore()
+}
+
+/// The patterns in `.notomatticignore`, without comments and blank lines
+fn read_ignore_patterns() -> Vec<String> {
+    fs::read_to_string(get_notes_dir().join(IGNORE_FILENAME))
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Replace the patterns in `.notomatticignore`, keeping its comments. Every
+/// pattern is checked first so a typo doesn't leave a half-written file.
+fn write_ignore_patterns(patterns: &[String]) -> Result<(), String> {
+    let notes_dir = get_notes_dir();
+    let mut builder = GitignoreBuilder::new(&notes_dir);
+    for pattern in patterns {
+        builder
+            .add_line(None, pattern)
+            .map_err(|e| format!("Invalid ignore pattern '{}': {}", pattern, e))?;
+    }
+
+    let ignore_path = notes_dir.join(IGNORE_FILENAME);
+    let mut lines: Vec<String> = fs::read_to_string(&ignore_path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| line.trim_start().starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    lines.extend(
+        patterns
+            .iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty()),
+    );
+
+    let mut content = lines.join("\n");
+    content.push('\n');
+    fs::write(&ignore_path, content).map_err(|e| e.to_string())
+}
+
 // Template System Helper Functions
 
 fn get_templates_dir() -> Result<PathBuf, String> {
-    let path = dirs::document_dir()
-        .ok_or("Could not find Documents directory")?
-        .join("Notomattic")
-        .join("templates");
-    Ok(path)
+    Ok(get_notes_dir().join("templates"))
 }
 
 fn ensure_templates_dir() -> Result<(), String> {
@@ -119,6 +592,8 @@ fn get_default_templates() -> Vec<Template> {
             icon: "users".to_string(),
             is_default: true,
             content: include_str!("templates/meeting-notes.md").to_string(),
+            category: Some("Work".to_string()),
+            is_snippet: false,
         },
         Template {
             id: "daily-log".to_string(),
@@ -127,6 +602,8 @@ fn get_default_templates() -> Vec<Template> {
             icon: "calendar".to_string(),
             is_default: true,
             content: include_str!("templates/daily-log.md").to_string(),
+            category: Some("Personal".to_string()),
+            is_snippet: false,
         },
         Template {
             id: "project-plan".to_string(),
@@ -135,20 +612,164 @@ fn get_default_templates() -> Vec<Template> {
             icon: "clipboard".to_string(),
             is_default: true,
             content: include_str!("templates/project-plan.md").to_string(),
+            category: Some("Work".to_string()),
+            is_snippet: false,
         },
     ]
 }
 
-fn replace_template_variables(content: String) -> String {
+fn builtin_template_variables() -> HashMap<String, String> {
     let now = Local::now();
-    let date = now.format("%Y-%m-%d").to_string();
-    let time = now.format("%H:%M").to_string();
-    let day_of_week = now.format("%A").to_string();
+    let yesterday = now - chrono::Duration::days(1);
+    let tomorrow = now + chrono::Duration::days(1);
+    let (week_start, week_end) = periodic::bounds(Period::Weekly, now.date_naive());
+    let (month_start, month_end) = periodic::bounds(Period::Monthly, now.date_naive());
+
+    HashMap::from([
+        ("date".to_string(), now.format("%Y-%m-%d").to_string()),
+        ("time".to_string(), now.format("%H:%M").to_string()),
+        ("day_of_week".to_string(), now.format("%A").to_string()),
+        ("year".to_string(), now.format("%Y").to_string()),
+        ("month".to_string(), now.format("%m").to_string()),
+        ("week_number".to_string(), now.format("%V").to_string()),
+        (
+            "yesterday".to_string(),
+            yesterday.format("%Y-%m-%d").to_string(),
+        ),
+        (
+            "tomorrow".to_string(),
+            tomorrow.format("%Y-%m-%d").to_string(),
+        ),
+        (
+            "week_start".to_string(),
+            week_start.format("%Y-%m-%d").to_string(),
+        ),
+        (
+            "week_end".to_string(),
+            week_end.format("%Y-%m-%d").to_string(),
+        ),
+        (
+            "month_start".to_string(),
+            month_start.format("%Y-%m-%d").to_string(),
+        ),
+        (
+            "month_end".to_string(),
+            month_end.format("%Y-%m-%d").to_string(),
+        ),
+    ])
+}
 
-    content
-        .replace("{{date}}", &date)
-        .replace("{{time}}", &time)
-        .replace("{{day_of_week}}", &day_of_week)
+/// Reject `{{placeholders}}` that aren't built-in variables, which are
+/// usually typos like `{{dat}}`
+fn validate_template_variables(content: &str) -> Result<(), String> {
+    let builtins = builtin_template_variables();
+    let mut unknown: Vec<&str> = TEMPLATE_VARIABLE_REGEX
+        .captures_iter(content)
+        .filter_map(|cap| cap.get(1))
+        .map(|name| name.as_str())
+        .filter(|name| !builtins.contains_key(*name))
+        .collect();
+    unknown.sort_unstable();
+    unknown.dedup();
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    let tokens: Vec<String> = unknown
+        .iter()
+        .map(|name| format!("{{{{{}}}}}", name))
+        .collect();
+    Err(format!("Unknown template variables: {}", tokens.join(", ")))
+}
+
+fn replace_template_variables(content: String) -> String {
+    replace_template_variables_with(content, &HashMap::new())
+}
+
+/// Substitute built-in and caller-supplied variables (the latter take
+/// precedence). Unknown placeholders are left intact so typos stay visible.
+fn replace_template_variables_with(content: String, custom: &HashMap<String, String>) -> String {
+    let builtins = builtin_template_variables();
+
+    TEMPLATE_VARIABLE_REGEX
+        .replace_all(&content, |cap: &regex::Captures| {
+            let name = &cap[1];
+            custom
+                .get(name)
+                .or_else(|| builtins.get(name))
+                .cloned()
+                .unwrap_or_else(|| cap[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Trimmed category name, with blank names meaning uncategorized
+fn normalize_template_category(category: Option<String>) -> Option<String> {
+    category
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+}
+
+/// Custom templates on disk with the file each was read from. For files
+/// sharing an id, the one named after it (`<id>.json`, as written by
+/// `save_template`) comes first, then the rest by filename.
+fn read_custom_templates() -> Result<Vec<(String, Template)>, String> {
+    let templates_dir = get_templates_dir()?;
+    let Ok(entries) = fs::read_dir(&templates_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut templates = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(file) = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        if !file.ends_with(".json") {
+            continue;
+        }
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(template) = serde_json::from_str::<Template>(&content) {
+                templates.push((file, template));
+            }
+        }
+    }
+
+    templates
+        .sort_by_key(|(file, template)| (*file != format!("{}.json", template.id), file.clone()));
+    Ok(templates)
+}
+
+/// Ids used by more than one template file, or by a file and a default
+fn find_template_conflicts(custom: &[(String, Template)]) -> Vec<TemplateConflict> {
+    let defaults = get_default_templates();
+    let mut by_id: Vec<(String, Vec<String>)> = Vec::new();
+
+    for (file, template) in custom {
+        match by_id.iter_mut().find(|(id, _)| *id == template.id) {
+            Some((_, files)) => files.push(file.clone()),
+            None => by_id.push((template.id.clone(), vec![file.clone()])),
+        }
+    }
+
+    let mut conflicts: Vec<TemplateConflict> = by_id
+        .into_iter()
+        .filter_map(|(id, files)| {
+            let shadows_default = defaults.iter().any(|t| t.id == id);
+            (files.len() > 1 || shadows_default).then_some(TemplateConflict {
+                id,
+                files,
+                shadows_default,
+            })
+        })
+        .collect();
+    conflicts.sort_by(|a, b| a.id.cmp(&b.id));
+    conflicts
 }
 
 fn generate_template_id(name: &str) -> String {
@@ -165,6 +786,15 @@ fn generate_template_id(name: &str) -> String {
 // Wiki Link System Helper Functions
 
 fn parse_wiki_links(content: &str) -> Vec<String> {
+    parse_wiki_links_with_headings(content)
+        .into_iter()
+        .map(|(target, _, _)| target)
+        .collect()
+}
+
+/// Parse wiki links, splitting `[[Note#Section]]` targets into the note name
+/// and an optional heading, alongside the link's display text
+fn parse_wiki_links_with_headings(content: &str) -> Vec<(String, Option<String>, String)> {
     let mut links = Vec::new();
 
     for cap in WIKI_LINK_REGEX.captures_iter(content) {
@@ -172,19 +802,131 @@ fn parse_wiki_links(content: &str) -> Vec<String> {
         let target = cap
             .get(2)
             .or_else(|| cap.get(1))
-            .map(|m| m.as_str().to_string())
+            .map(|m| m.as_str())
             .unwrap_or_default();
+        // What the reader sees: the part before the pipe, or the whole link
+        let display = cap.get(1).map(|m| m.as_str().trim()).unwrap_or_default();
+
+        let (note, heading) = match target.split_once('#') {
+            Some((note, heading)) => (note.trim(), Some(heading.trim().to_string())),
+            None => (target, None),
+        };
+
+        if !note.is_empty() {
+            links.push((
+                note.to_string(),
+                heading.filter(|h| !h.is_empty()),
+                display.to_string(),
+            ));
+        }
+    }
+
+    links
+}
 
-        if !target.is_empty() {
-            links.push(target);
+/// Decode `%20`-style escapes in a link path, leaving malformed ones as written
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Parse markdown links to local `.md` files, e.g. `[Plan](../notes/plan.md)`,
+/// as written by other editors. Returns each target's note name (the file
+/// stem) with the link text it came from.
+fn parse_markdown_note_links(content: &str) -> Vec<(String, String)> {
+    let mut links = Vec::new();
+
+    for cap in MARKDOWN_NOTE_LINK_REGEX.captures_iter(content) {
+        let whole = cap.get(0).unwrap();
+        // Images share the syntax, with a leading `!`
+        if content[..whole.start()].ends_with('!') {
+            continue;
+        }
+
+        let path = percent_decode(&cap[1]);
+        if path.contains("://") {
+            continue;
+        }
+
+        if let Some(name) = Path::new(&path).file_stem().and_then(|s| s.to_str()) {
+            if !name.is_empty() {
+                links.push((name.to_string(), whole.as_str().to_string()));
+            }
         }
     }
 
     links
 }
 
+/// Wiki links, plus markdown links to local notes when `include_markdown` is set
+fn parse_note_links(content: &str, include_markdown: bool) -> Vec<String> {
+    let mut links = parse_wiki_links(content);
+    if include_markdown {
+        links.extend(
+            parse_markdown_note_links(content)
+                .into_iter()
+                .map(|(name, _)| name),
+        );
+    }
+    links
+}
+
 fn note_name_to_filename(note_name: &str) -> String {
-    // Convert "Meeting Notes" -> "meeting-notes.md"
+    note_name_to_filename_with(note_name, settings::naming_strategy())
+}
+
+/// The filename a note name maps to under a given naming strategy
+fn note_name_to_filename_with(note_name: &str, strategy: NamingStrategy) -> String {
+    match strategy {
+        NamingStrategy::Slug => {
+            // Decompose accented letters and drop the accents, so "Café" and
+            // "Cafe" both become "cafe.md"
+            let unaccented: String = note_name.nfd().filter(|c| !is_combining_mark(*c)).collect();
+            slugify_note_name(&unaccented)
+        }
+        NamingStrategy::Verbatim => format!("{}.md", verbatim_note_stem(note_name)),
+        NamingStrategy::Underscore => {
+            let stem = verbatim_note_stem(note_name);
+            format!(
+                "{}.md",
+                stem.split_whitespace().collect::<Vec<_>>().join("_")
+            )
+        }
+    }
+}
+
+/// A note name with the characters filenames can't hold removed, keeping
+/// case, spaces and punctuation like `C++`
+fn verbatim_note_stem(note_name: &str) -> String {
+    let kept: String = note_name
+        .chars()
+        .filter(|c| !c.is_control() && !FORBIDDEN_FILENAME_CHARS.contains(c))
+        .collect();
+    kept.trim().trim_matches('.').trim().to_string()
+}
+
+/// Convert "Meeting Notes" -> "meeting-notes.md"
+fn slugify_note_name(note_name: &str) -> String {
     let slug = note_name
         .to_lowercase()
         .trim()
@@ -195,14 +937,60 @@ fn note_name_to_filename(note_name: &str) -> String {
 }
 
 fn note_exists(note_name: &str) -> Result<(bool, String), String> {
-    let notes_dir = get_notes_dir();
+    let standalone_dir = get_standalone_dir();
+    Ok(resolve_note_name(note_name, |filename, is_daily| {
+        if is_daily {
+            get_daily_note_dir(filename).join(filename).exists()
+        } else {
+            standalone_dir.join(filename).exists()
+        }
+    }))
+}
+
+/// Daily note filename for a link that names a date: `YYYY-MM-DD`, or
+/// `today`, `yesterday` or `tomorrow` relative to the current day
+fn date_phrase_filename(note_name: &str) -> Option<String> {
+    let today = Local::now().date_naive();
+    let date = match note_name.trim().to_lowercase().as_str() {
+        "today" => today,
+        "yesterday" => today - chrono::Duration::days(1),
+        "tomorrow" => today + chrono::Duration::days(1),
+        other => chrono::NaiveDate::parse_from_str(other, "%Y-%m-%d").ok()?,
+    };
+    Some(format!("{}.md", date.format("%Y-%m-%d")))
+}
+
+/// Resolve a link name to a note filename, asking `has_note(filename,
+/// is_daily)` whether each candidate exists. Returns whether one did, and
+/// the filename (or the one a new note would get).
+fn resolve_note_name(note_name: &str, has_note: impl Fn(&str, bool) -> bool) -> (bool, String) {
+    // Dates, including `[[yesterday]]`, point at that day's note when it exists
+    if let Some(daily_filename) = date_phrase_filename(note_name) {
+        if has_note(&daily_filename, true) {
+            return (true, daily_filename);
+        }
+    }
 
-    // Try as standalone note first
+    // Then try as standalone note
     let filename = note_name_to_filename(note_name);
-    let standalone_path = notes_dir.join("notes").join(&filename);
+    if has_note(&filename, false) {
+        return (true, filename);
+    }
+
+    // Notes created under another naming strategy keep their filenames
+    for strategy in NamingStrategy::ALL {
+        let other = note_name_to_filename_with(note_name, strategy);
+        if other != filename && has_note(&other, false) {
+            return (true, other);
+        }
+    }
 
-    if standalone_path.exists() {
-        return Ok((true, filename));
+    // Notes created before accents were stripped kept them in the filename
+    if !note_name.is_ascii() {
+        let accented = slugify_note_name(note_name);
+        if accented != filename && has_note(&accented, false) {
+            return (true, accented);
+        }
     }
 
     // Try as daily note (YYYY-MM-DD format)
@@ -211,71 +999,489 @@ fn note_exists(note_name: &str) -> Result<(bool, String), String> {
     } else {
         format!("{}.md", note_name)
     };
-    let daily_path = notes_dir.join("daily").join(&daily_filename);
-    if daily_path.exists() {
-        return Ok((true, daily_filename));
+    if has_note(&daily_filename, true) {
+        return (true, daily_filename);
+    }
+
+    // Finally, any note listing the name in its frontmatter `aliases`
+    if let Some(aliased) = aliases::resolve(note_name) {
+        return (true, aliased);
     }
 
-    Ok((false, filename))
+    (false, filename)
 }
 
-fn get_link_context(content: &str, link_text: &str) -> String {
-    // Try both with and without pipe syntax
-    let search_patterns = vec![
-        format!("[[{}]]", link_text),
-        format!("[[{}|", link_text),
-    ];
+/// Locate a resolved note filename on disk, checking standalone notes first
+/// like `note_exists` does. Returns the path and whether it's a daily note.
+fn find_note_file(filename: &str) -> Option<(PathBuf, bool)> {
+    [
+        (get_standalone_dir(), false),
+        (get_daily_note_dir(filename), true),
+    ]
+    .into_iter()
+    .map(|(dir, is_daily)| (dir.join(filename), is_daily))
+    .find(|(path, _)| path.exists())
+}
 
-    for search in search_patterns {
-        if let Some(pos) = content.find(&search) {
-            let start = pos.saturating_sub(50);
-            let end = (pos + search.len() + 50).min(content.len());
-
-            // Find the actual end of the link
-            let actual_end = if search.ends_with('|') {
-                // Find the closing ]]
-                content[pos..]
-                    .find("]]")
-                    .map(|p| (pos + p + 2 + 50).min(content.len()))
-                    .unwrap_or(end)
-            } else {
-                end
-            };
+/// Check whether a note contains a heading (any level) matching `heading`,
+/// compared case-insensitively
+fn note_has_heading(filename: &str, heading: &str) -> bool {
+    let wanted = heading.trim().to_lowercase();
+
+    find_note_file(filename)
+        .and_then(|(path, _)| fs::read_to_string(path).ok())
+        .map(|content| {
+            content.lines().any(|line| {
+                let line = line.trim();
+                line.starts_with('#')
+                    && line.trim_start_matches('#').trim().to_lowercase() == wanted
+            })
+        })
+        .unwrap_or(false)
+}
 
-            let context = &content[start..actual_end];
+/// Byte range of the first wiki link to the note `link_text`, whether
+/// plain, piped (`[[Display|target]]`) or to a heading (`[[target#Heading]]`)
+fn find_wiki_link(content: &str, link_text: &str) -> Option<(usize, usize)> {
+    WIKI_LINK_REGEX.captures_iter(content).find_map(|cap| {
+        // The target is after the pipe when there is one, as in
+        // `parse_wiki_links_with_headings`
+        let target = cap.get(2).or_else(|| cap.get(1))?.as_str();
+        let note = target
+            .split_once('#')
+            .map_or(target, |(note, _)| note.trim());
+        let whole = cap.get(0)?;
+        (note == link_text).then(|| (whole.start(), whole.end()))
+    })
+}
 
-            // Add ellipsis if truncated
-            let mut result = String::new();
-            if start > 0 {
-                result.push_str("...");
-            }
-            result.push_str(context);
-            if actual_end < content.len() {
-                result.push_str("...");
-            }
+/// How much text either side of a link backlink contexts show by default
+const LINK_CONTEXT_WINDOW: usize = 50;
 
-            return result;
-        }
+/// Byte offset `count` characters before `pos`, or the start of `content`.
+/// Counting characters rather than bytes keeps the offset on a character
+/// boundary, so slicing there can't split emoji or CJK text.
+fn offset_chars_before(content: &str, pos: usize, count: usize) -> usize {
+    if count == 0 {
+        return pos;
     }
+    content[..pos]
+        .char_indices()
+        .rev()
+        .take(count)
+        .last()
+        .map_or(pos, |(i, _)| i)
+}
 
-    String::new()
+/// Byte offset `count` characters after `pos`, or the end of `content`
+fn offset_chars_after(content: &str, pos: usize, count: usize) -> usize {
+    content[pos..]
+        .char_indices()
+        .nth(count)
+        .map_or(content.len(), |(i, _)| pos + i)
 }
 
-// Wiki Link System Commands
+/// Text around the first wiki link to `link_text`, `window` characters
+/// either side
+fn get_link_context(content: &str, link_text: &str, window: usize) -> String {
+    let Some((link_start, link_end)) = find_wiki_link(content, link_text) else {
+        return String::new();
+    };
 
-#[tauri::command]
-fn scan_note_links(content: String) -> Result<Vec<WikiLink>, String> {
-    let link_names = parse_wiki_links(&content);
-    let mut wiki_links = Vec::new();
+    let start = offset_chars_before(content, link_start, window);
+    let end = offset_chars_after(content, link_end, window);
+    context_around(content, start, end)
+}
+
+/// Follows the current heading (any level) as a note is read line by line,
+/// ignoring frontmatter and fenced code blocks
+#[derive(Default)]
+struct SectionTracker {
+    lines_seen: usize,
+    in_frontmatter: bool,
+    in_code_fence: bool,
+    heading: Option<String>,
+}
+
+impl SectionTracker {
+    fn feed(&mut self, line: &str) {
+        let trimmed = line.trim();
+        self.lines_seen += 1;
+
+        if self.lines_seen == 1 && trimmed == "---" {
+            self.in_frontmatter = true;
+            return;
+        }
+        if self.in_frontmatter {
+            if trimmed == "---" {
+                self.in_frontmatter = false;
+            }
+            return;
+        }
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            self.in_code_fence = !self.in_code_fence;
+            return;
+        }
+        if self.in_code_fence {
+            return;
+        }
+
+        if let Some((_, text)) = export::parse_heading(line) {
+            self.heading = Some(text.to_string());
+        }
+    }
+}
+
+/// Where `find_link_in_file` found a link
+struct LinkMatch {
+    context: String,
+    section: Option<String>,
+}
+
+/// Read a note line by line, stopping at the first line that links with
+/// `wiki_link` or `markdown_name`. The context is taken from that line and
+/// its neighbours, so the rest of the file is never read; a `window` wider
+/// than those lines is cut short.
+fn find_link_in_file(
+    path: &Path,
+    wiki_link: Option<&str>,
+    markdown_name: Option<&str>,
+    window: usize,
+) -> Result<Option<LinkMatch>, String> {
+    let read_error = |e: std::io::Error| format!("Failed to read file: {}", e);
+    let file = fs::File::open(path).map_err(read_error)?;
+    let mut lines = std::io::BufReader::new(file).lines();
+
+    let mut sections = SectionTracker::default();
+    let mut previous = String::new();
+
+    while let Some(line) = lines.next() {
+        let line = line.map_err(read_error)?;
+        sections.feed(&line);
+
+        let markdown = markdown_name.and_then(|name| {
+            parse_markdown_note_links(&line)
+                .into_iter()
+                .find(|(link_name, _)| link_name == name)
+                .map(|(_, markdown)| markdown)
+        });
+        let wiki = wiki_link.filter(|link| find_wiki_link(&line, link).is_some());
+        if wiki.is_none() && markdown.is_none() {
+            previous = line;
+            continue;
+        }
+
+        let next = lines.next().and_then(Result::ok).unwrap_or_default();
+        let text = [previous.as_str(), line.as_str(), next.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let context = match (wiki, markdown) {
+            (Some(link), _) => get_link_context(&text, link, window),
+            (None, Some(markdown)) => get_markdown_link_context(&text, &markdown, window),
+            (None, None) => String::new(),
+        };
+
+        return Ok(Some(LinkMatch {
+            context,
+            section: sections.heading,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Context for a markdown link, found by the exact text it was written as
+fn get_markdown_link_context(content: &str, link_markdown: &str, window: usize) -> String {
+    match content.find(link_markdown) {
+        Some(pos) => context_around(
+            content,
+            offset_chars_before(content, pos, window),
+            offset_chars_after(content, pos + link_markdown.len(), window),
+        ),
+        None => String::new(),
+    }
+}
+
+/// `content[start..end]` widened to whole characters, with ellipses where
+/// it was cut from the rest of the note
+fn context_around(content: &str, mut start: usize, mut end: usize) -> String {
+    while !content.is_char_boundary(start) {
+        start -= 1;
+    }
+    while !content.is_char_boundary(end) {
+        end += 1;
+    }
+
+    // Add ellipsis if truncated
+    let mut result = String::new();
+    if start > 0 {
+        result.push_str("...");
+    }
+    result.push_str(&content[start..end]);
+    if end < content.len() {
+        result.push_str("...");
+    }
+
+    result
+}
+
+fn first_heading(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find(|line| line.starts_with("# "))
+        .map(|line| line.trim_start_matches("# ").to_string())
+}
+
+/// Title a note is shown under: its frontmatter `title`, then its first
+/// `# ` heading, then its filename without the extension
+fn note_title(filename: &str, content: &str) -> String {
+    let (frontmatter, body) = frontmatter::parse_frontmatter(content);
+    frontmatter
+        .and_then(|fm| fm.title)
+        .or_else(|| first_heading(&body))
+        .unwrap_or_else(|| filename.trim_end_matches(".md").to_string())
+}
+
+/// Rank a candidate name against a lowercase prefix: exact match first, then
+/// names starting with the prefix, then names with a word starting with it
+fn suggestion_rank(name: &str, prefix: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    if name == prefix {
+        Some(0)
+    } else if name.starts_with(prefix) {
+        Some(1)
+    } else if name
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .any(|word| word.starts_with(prefix))
+    {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Point every wiki link that resolves to `old_filename` at `new_target`,
+/// keeping piped display text and `#heading` suffixes. Returns `None` when
+/// nothing in the content changed.
+fn rewrite_links_to(content: &str, old_filename: &str, new_target: &str) -> Option<String> {
+    let old_name = old_filename.trim_end_matches(".md");
+    let mut changed = false;
+
+    let rewritten = WIKI_LINK_REGEX.replace_all(content, |cap: &regex::Captures| {
+        let original = cap[0].to_string();
+        let raw_target = cap.get(2).or_else(|| cap.get(1)).map_or("", |m| m.as_str());
+        let (note, heading) = match raw_target.split_once('#') {
+            Some((note, heading)) => (note.trim(), Some(heading)),
+            None => (raw_target.trim(), None),
+        };
+
+        let (_, resolved) = note_exists(note).unwrap_or((false, String::new()));
+        if resolved != old_filename && note != old_name {
+            return original;
+        }
+
+        changed = true;
+        let target = match heading {
+            Some(h) => format!("{}#{}", new_target, h),
+            None => new_target.to_string(),
+        };
+        match cap.get(2) {
+            Some(_) => format!("[[{}|{}]]", &cap[1], target),
+            None => format!("[[{}]]", target),
+        }
+    });
+
+    if changed {
+        Some(rewritten.into_owned())
+    } else {
+        None
+    }
+}
+
+/// The lines under a heading (any level, matched case-insensitively), up to
+/// the next heading of the same or a higher level
+fn extract_section(content: &str, heading: &str) -> Option<String> {
+    let wanted = heading.trim().to_lowercase();
+    let heading_level = |line: &str| {
+        let level = line.chars().take_while(|&c| c == '#').count();
+        (level > 0 && line[level..].starts_with(' ')).then_some(level)
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let (start, level) = lines.iter().enumerate().find_map(|(i, line)| {
+        let level = heading_level(line)?;
+        (line[level..].trim().to_lowercase() == wanted).then_some((i, level))
+    })?;
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| heading_level(line).is_some_and(|l| l <= level))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+
+    Some(lines[start..end].join("\n").trim_end().to_string())
+}
+
+/// Replace `![[Note]]` (or `![[Note#Section]]`) markers with the body of the
+/// embedded note, recursing up to `depth` levels. Embeds past the depth limit
+/// or that would embed a note inside itself are left as written.
+fn inline_embeds(content: &str, depth: u32, embedding: &mut Vec<String>) -> String {
+    if depth == 0 {
+        return content.to_string();
+    }
+
+    EMBED_REGEX
+        .replace_all(content, |cap: &regex::Captures| {
+            let original = cap[0].to_string();
+            let raw_target = cap.get(2).or_else(|| cap.get(1)).map_or("", |m| m.as_str());
+            let (note, heading) = match raw_target.split_once('#') {
+                Some((note, heading)) => (note.trim(), Some(heading.trim())),
+                None => (raw_target.trim(), None),
+            };
+            let missing = || format!("> Embedded note not found: {}", raw_target.trim());
+
+            let filename = match note_exists(note) {
+                Ok((true, filename)) => filename,
+                _ => return missing(),
+            };
+            if embedding.contains(&filename) {
+                return original;
+            }
+            let Some(content) =
+                find_note_file(&filename).and_then(|(path, _)| fs::read_to_string(path).ok())
+            else {
+                return missing();
+            };
+
+            let (_, body) = frontmatter::parse_frontmatter(&content);
+            let body = match heading.filter(|h| !h.is_empty()) {
+                Some(heading) => match extract_section(&body, heading) {
+                    Some(section) => section,
+                    None => return missing(),
+                },
+                None => body.trim().to_string(),
+            };
+
+            embedding.push(filename);
+            let inlined = inline_embeds(&body, depth - 1, embedding);
+            embedding.pop();
+            inlined
+        })
+        .into_owned()
+}
+
+/// Minimum similarity for an existing note to be offered as "did you mean"
+const SUGGESTION_THRESHOLD: f64 = 0.6;
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Similarity of two slugs between 0 and 1: the better of their edit
+/// distance relative to length and the overlap of their `-`-separated words
+fn name_similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+    let edit = 1.0 - levenshtein(a, b) as f64 / longest as f64;
+
+    let words_a: HashSet<&str> = a.split('-').filter(|w| !w.is_empty()).collect();
+    let words_b: HashSet<&str> = b.split('-').filter(|w| !w.is_empty()).collect();
+    let union = words_a.union(&words_b).count();
+    let overlap = if union == 0 {
+        0.0
+    } else {
+        words_a.intersection(&words_b).count() as f64 / union as f64
+    };
+
+    edit.max(overlap)
+}
+
+/// Filenames of every (non-ignored) note, standalone notes first
+fn note_filenames() -> Vec<String> {
+    let ignore_rules = load_ignore_rules();
+    list_note_paths(false)
+        .into_iter()
+        .chain(list_note_paths(true))
+        .filter(|path| !is_ignored(&ignore_rules, path))
+        .filter_map(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .collect()
+}
+
+/// The existing note whose name is most similar to an unresolved link, if
+/// any is similar enough to be worth suggesting
+fn closest_note(link: &str, filenames: &[String]) -> Option<String> {
+    let wanted = note_name_to_filename(link);
+    let wanted = wanted.trim_end_matches(".md");
+
+    filenames
+        .iter()
+        .map(|filename| {
+            let stem = filename.trim_end_matches(".md").to_lowercase();
+            (name_similarity(wanted, &stem), filename)
+        })
+        .filter(|(score, _)| *score >= SUGGESTION_THRESHOLD)
+        // Earlier (standalone) notes win ties
+        .fold(None, |best: Option<(f64, &String)>, candidate| match best {
+            Some(best) if best.0 >= candidate.0 => Some(best),
+            _ => Some(candidate),
+        })
+        .map(|(_, filename)| filename.clone())
+}
+
+// Wiki Link System Commands
+
+/// Parse and resolve every wiki link in a note, flagging missing notes and
+/// headings
+fn resolve_wiki_links(content: &str) -> Result<Vec<WikiLink>, String> {
+    let link_names = parse_wiki_links_with_headings(content);
+    let mut wiki_links = Vec::new();
+    // Only listed if some link fails to resolve
+    let mut filenames: Option<Vec<String>> = None;
 
-    for name in link_names {
+    for (name, heading, display) in link_names {
         let (exists, target) =
             note_exists(&name).map_err(|e| format!("Failed to check note existence: {}", e))?;
 
+        // Missing headings still resolve to the note, just flagged as not found
+        let heading_found = heading
+            .as_deref()
+            .map(|h| exists && note_has_heading(&target, h));
+
+        // Offered as "did you mean", never used to resolve the link
+        let suggestion = if exists {
+            None
+        } else {
+            closest_note(&name, filenames.get_or_insert_with(note_filenames))
+        };
+
         wiki_links.push(WikiLink {
             text: name.clone(),
+            display,
             target,
             exists,
+            heading,
+            heading_found,
+            suggestion,
         });
     }
 
@@ -283,101 +1489,543 @@ fn scan_note_links(content: String) -> Result<Vec<WikiLink>, String> {
 }
 
 #[tauri::command]
-fn get_backlinks(filename: String) -> Result<Vec<BacklinkInfo>, String> {
+fn scan_note_links(content: String) -> Result<Vec<WikiLink>, String> {
+    resolve_wiki_links(&content)
+}
+
+/// `scan_note_links` checked against note filenames the caller already has
+/// (e.g. from `list_notes`) instead of the disk, for re-scanning as the user
+/// types. Only links matching none of them fall back to frontmatter aliases.
+/// Headings aren't looked up, so `heading_found` is always `None`.
+#[tauri::command]
+fn scan_note_links_with_index(content: String, existing_names: Vec<String>) -> Vec<WikiLink> {
+    let filenames: Vec<String> = existing_names
+        .into_iter()
+        .map(|name| {
+            if name.ends_with(".md") {
+                name
+            } else {
+                format!("{}.md", name)
+            }
+        })
+        .collect();
+    let known: HashSet<&str> = filenames.iter().map(String::as_str).collect();
+
+    parse_wiki_links_with_headings(&content)
+        .into_iter()
+        .map(|(name, heading, display)| {
+            let (exists, target) = resolve_note_name(&name, |filename, _| known.contains(filename));
+            let suggestion = if exists {
+                None
+            } else {
+                closest_note(&name, &filenames)
+            };
+
+            WikiLink {
+                text: name,
+                display,
+                target,
+                exists,
+                heading,
+                heading_found: None,
+                suggestion,
+            }
+        })
+        .collect()
+}
+
+/// Links going out of a saved note, resolved like `scan_note_links`; the
+/// counterpart to `get_backlinks`
+#[tauri::command]
+fn get_outgoing_links(filename: String, is_daily: bool) -> Result<Vec<WikiLink>, String> {
+    let content =
+        fs::read_to_string(existing_note_path(&filename, is_daily)?).map_err(|e| e.to_string())?;
+    resolve_wiki_links(&content)
+}
+
+/// Inline the notes referenced by `![[Note]]` embeds, at most `depth` levels
+/// deep. Embeds of missing notes or sections become a visible placeholder.
+#[tauri::command]
+fn resolve_embeds(content: String, depth: u32) -> Result<String, String> {
+    Ok(inline_embeds(&content, depth, &mut Vec::new()))
+}
+
+/// How many other notes link to `filename`, answered from the link index
+/// without reading any note content
+#[tauri::command]
+fn count_backlinks(
+    filename: String,
+    include_markdown_links: Option<bool>,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<usize, String> {
+    let cache = link_index.0.lock().map_err(|e| e.to_string())?;
+    Ok(cache.count_linking_to(&filename, include_markdown_links.unwrap_or(false)))
+}
+
+/// Report how far a long-running scan has got. Events are limited to about
+/// one per percent so big vaults don't flood the frontend.
+fn emit_scan_progress(app: &AppHandle, scan: &str, processed: usize, total: usize) {
+    let percent = |count: usize| count * 100 / total.max(1);
+    if processed != 0 && processed != total && percent(processed) == percent(processed - 1) {
+        return;
+    }
+
+    let event = ScanProgressEvent {
+        scan: scan.to_string(),
+        processed,
+        total,
+    };
+    if let Err(e) = app.emit("scan-progress", event) {
+        log::warn!("Failed to emit scan-progress event: {}", e);
+    }
+}
+
+/// Read every note in the vault in path order, calling `visit` with each
+/// note and its content while emitting `scan-progress` events. Notes that
+/// can't be read are skipped.
+fn scan_vault(
+    app: &AppHandle,
+    scan: &str,
+    mut visit: impl FnMut(&NoteFile, &str),
+) -> Result<(), String> {
     let notes_dir = get_notes_dir();
-    let mut backlinks = Vec::new();
+    let mut notes = collect_notes()?;
+    notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let total = notes.len();
+    emit_scan_progress(app, scan, 0, total);
+
+    for (i, note) in notes.iter().enumerate() {
+        if let Ok(content) = fs::read_to_string(notes_dir.join(&note.path)) {
+            visit(note, &content);
+        }
+        emit_scan_progress(app, scan, i + 1, total);
+    }
+
+    Ok(())
+}
+
+/// Notes bigger than this are listed as backlinks without being read
+const MAX_BACKLINK_SCAN_BYTES: u64 = 1024 * 1024;
+
+/// A note linking to the one being viewed, with the first wiki link and
+/// markdown link (by note name) to look for when pulling its context
+struct LinkingSource {
+    source: link_index::SourceEntry,
+    wiki_link: Option<String>,
+    markdown_link: Option<String>,
+}
+
+/// Narrow the indexed sources of `filename` to the notes that still link to
+/// it, once each however many of their links (by name, alias or markdown
+/// link) point there. `from_note` is all the frontend sees, so a daily and a
+/// standalone note sharing a filename count as one
+fn linking_sources(
+    sources: Vec<link_index::SourceEntry>,
+    filename: &str,
+    include_markdown_links: bool,
+) -> Vec<LinkingSource> {
+    let mut listed: HashSet<String> = HashSet::new();
 
     // Get the note name from filename (for matching)
     let note_name = filename.trim_end_matches(".md");
 
-    // Scan all notes (daily + standalone)
-    let daily_dir = notes_dir.join("daily");
-    let standalone_dir = notes_dir.join("notes");
+    let mut linking = Vec::new();
+    for source in sources {
+        // Don't include self-links
+        if source.filename == filename {
+            continue;
+        }
 
-    for dir in [daily_dir, standalone_dir] {
-        if !dir.exists() {
+        let links_here =
+            |(link, target): &&(String, String)| *target == filename || link == note_name;
+        let wiki_link = source
+            .links
+            .iter()
+            .find(links_here)
+            .map(|(link, _)| link.clone());
+        let markdown_link = if include_markdown_links {
+            source
+                .markdown_links
+                .iter()
+                .find(links_here)
+                .map(|(name, _)| name.clone())
+        } else {
+            None
+        };
+        if wiki_link.is_none() && markdown_link.is_none() {
             continue;
         }
+        if !listed.insert(source.filename.clone()) {
+            continue;
+        }
+        linking.push(LinkingSource {
+            source,
+            wiki_link,
+            markdown_link,
+        });
+    }
+    linking
+}
+
+#[tauri::command]
+fn get_backlinks(
+    app: AppHandle,
+    filename: String,
+    include_markdown_links: Option<bool>,
+    max_file_size: Option<u64>,
+    context_size: Option<usize>,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<Vec<BacklinkInfo>, String> {
+    let context_size = context_size.unwrap_or(LINK_CONTEXT_WINDOW);
+    let include_markdown_links = include_markdown_links.unwrap_or(false);
+    let max_file_size = max_file_size.unwrap_or(MAX_BACKLINK_SCAN_BYTES);
+    let sources = {
+        let cache = link_index.0.lock().map_err(|e| e.to_string())?;
+        cache.sources_linking_to(&filename, include_markdown_links)
+    };
+    let linking = linking_sources(sources, &filename, include_markdown_links);
+    let mut backlinks = Vec::new();
 
-        let entries =
-            std::fs::read_dir(&dir).map_err(|e| format!("Failed to read directory: {}", e))?;
+    // The index narrows the scan to linking notes, so progress counts those
+    let total = linking.len();
+    emit_scan_progress(&app, "get_backlinks", 0, total);
+
+    for (i, linking_source) in linking.into_iter().enumerate() {
+        emit_scan_progress(&app, "get_backlinks", i + 1, total);
+        let LinkingSource {
+            source,
+            wiki_link,
+            markdown_link,
+        } = linking_source;
+
+        // Only the linking notes are read, to pull the surrounding context
+        let dir = if source.is_daily {
+            get_daily_note_dir(&source.filename)
+        } else {
+            get_standalone_dir()
+        };
+        let path = dir.join(&source.filename);
+
+        let too_large = fs::metadata(&path).is_ok_and(|m| m.len() > max_file_size);
+        let found = if too_large {
+            None
+        } else {
+            // In the index but no longer in the file means an empty context
+            find_link_in_file(
+                &path,
+                wiki_link.as_deref(),
+                markdown_link.as_deref(),
+                context_size,
+            )?
+        };
+
+        let (context, section) = found.map(|m| (m.context, m.section)).unwrap_or_default();
+
+        backlinks.push(BacklinkInfo {
+            from_note: source.filename,
+            from_title: source.title,
+            context,
+            section,
+            too_large,
+        });
+    }
 
-        for entry in entries.flatten() {
-            let path = entry.path();
+    Ok(backlinks)
+}
 
-            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("md") {
+/// A note that is only scaffolding: headings, empty bullets and rules with
+/// no actual writing (e.g. a daily note created from a template and left blank)
+fn is_empty_scaffold(content: &str) -> bool {
+    let (_, body) = frontmatter::parse_frontmatter(content);
+
+    body.lines().map(str::trim).all(|line| {
+        line.is_empty()
+            || line.starts_with('#')
+            || line == "---"
+            || line == "***"
+            || matches!(line, "-" | "*" | "+" | "- [ ]" | "* [ ]" | ">")
+            || line
+                .strip_suffix(['.', ')'])
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+#[tauri::command]
+fn find_orphans(app: AppHandle) -> Result<Vec<NoteFile>, String> {
+    let mut notes = Vec::new();
+    let mut connected: HashSet<String> = HashSet::new();
+    let mut empty_dailies: HashSet<String> = HashSet::new();
+
+    scan_vault(&app, "find_orphans", |note, content| {
+        notes.push(note.clone());
+
+        if note.is_daily && is_empty_scaffold(content) {
+            empty_dailies.insert(note.path.clone());
+        }
+
+        for link in parse_wiki_links(content) {
+            let Ok((true, target)) = note_exists(&link) else {
                 continue;
+            };
+            // Keyed by vault-relative path, so a daily and a standalone note
+            // sharing a filename don't share connectivity
+            let Some((_, is_daily)) = find_note_file(&target) else {
+                continue;
+            };
+            let key = pins::note_key(&target, is_daily);
+            if key != note.path {
+                connected.insert(note.path.clone());
+                connected.insert(key);
             }
+        }
+    })?;
+
+    let orphans = notes
+        .into_iter()
+        .filter(|note| !connected.contains(&note.path) || empty_dailies.contains(&note.path))
+        .collect();
+
+    Ok(orphans)
+}
+
+/// Groups of notes with near-identical content, most similar first.
+/// `threshold` is the Jaccard similarity (0-1) of their word shingles at
+/// which two notes count as duplicates.
+#[tauri::command]
+fn find_duplicate_notes(app: AppHandle, threshold: f32) -> Result<Vec<DuplicateGroup>, String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(format!(
+            "Threshold must be between 0 and 1, got {}",
+            threshold
+        ));
+    }
+
+    let mut refs = Vec::new();
+    let mut shingles = Vec::new();
+    scan_vault(&app, "find_duplicate_notes", |note, content| {
+        refs.push(NoteRef {
+            filename: note.name.clone(),
+            is_daily: note.is_daily,
+        });
+        shingles.push(duplicates::shingles(content));
+    })?;
+
+    let mut groups: Vec<DuplicateGroup> = duplicates::group(&shingles, threshold)
+        .into_iter()
+        .map(|(members, similarity)| DuplicateGroup {
+            notes: members.into_iter().map(|i| refs[i].clone()).collect(),
+            similarity,
+        })
+        .collect();
+    groups.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
 
-            let from_filename = path
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("")
-                .to_string();
+    Ok(groups)
+}
 
-            // Don't include self-links
-            if from_filename == filename {
+#[tauri::command]
+fn find_broken_links(app: AppHandle) -> Result<Vec<BrokenLink>, String> {
+    let mut broken = Vec::new();
+
+    // Notes are visited in path order, keeping each one's broken links together
+    scan_vault(&app, "find_broken_links", |note, content| {
+        let mut seen: HashSet<String> = HashSet::new();
+        for link in parse_wiki_links(content) {
+            if !seen.insert(link.clone()) {
+                continue;
+            }
+            if let Ok((true, _)) = note_exists(&link) {
                 continue;
             }
 
-            let content =
-                std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+            broken.push(BrokenLink {
+                from_note: note.name.clone(),
+                is_daily: note.is_daily,
+                context: get_link_context(content, &link, LINK_CONTEXT_WINDOW),
+                link,
+            });
+        }
+    })?;
 
-            let links = parse_wiki_links(&content);
+    Ok(broken)
+}
 
-            // Check if this note links to our target
-            for link in links {
-                let (_, target) = note_exists(&link).unwrap_or((false, String::new()));
+#[tauri::command]
+fn get_link_graph(
+    include_unresolved: Option<bool>,
+    include_markdown_links: Option<bool>,
+) -> Result<LinkGraph, String> {
+    let include_unresolved = include_unresolved.unwrap_or(false);
+    let include_markdown_links = include_markdown_links.unwrap_or(false);
+    let notes_dir = get_notes_dir();
 
-                if target == filename || link == note_name {
-                    let context = get_link_context(&content, &link);
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let mut seen_edges: HashSet<(String, String)> = HashSet::new();
+    let mut dangling: Vec<GraphNode> = Vec::new();
+    let mut seen_dangling: HashSet<String> = HashSet::new();
 
-                    // Extract title from first heading
-                    let title = content
-                        .lines()
-                        .find(|line| line.starts_with("# "))
-                        .map(|line| line.trim_start_matches("# ").to_string())
-                        .unwrap_or(from_filename.clone());
+    for note in collect_notes()? {
+        let Ok(content) = fs::read_to_string(notes_dir.join(&note.path)) else {
+            continue;
+        };
+        let (_, body) = frontmatter::parse_frontmatter(&content);
 
-                    backlinks.push(BacklinkInfo {
-                        from_note: from_filename.clone(),
-                        from_title: title,
-                        context,
-                    });
+        for link in parse_note_links(&body, include_markdown_links) {
+            let (exists, target) = note_exists(&link)?;
 
-                    break; // Only add once per note
+            let to = if exists {
+                match find_note_file(&target) {
+                    Some((_, is_daily)) => pins::note_key(&target, is_daily),
+                    None => continue,
+                }
+            } else if include_unresolved {
+                // Unresolved links would be created as standalone notes
+                let key = pins::note_key(&target, false);
+                if seen_dangling.insert(key.clone()) {
+                    dangling.push(GraphNode {
+                        id: key.clone(),
+                        title: link.clone(),
+                        is_daily: false,
+                        exists: false,
+                    });
                 }
+                key
+            } else {
+                continue;
+            };
+
+            if to != note.path && seen_edges.insert((note.path.clone(), to.clone())) {
+                edges.push(GraphEdge {
+                    from: note.path.clone(),
+                    to,
+                });
             }
         }
+
+        let title = note_title(&note.name, &content);
+
+        nodes.push(GraphNode {
+            id: note.path,
+            title,
+            is_daily: note.is_daily,
+            exists: true,
+        });
     }
 
-    Ok(backlinks)
+    nodes.extend(dangling);
+
+    Ok(LinkGraph { nodes, edges })
+}
+
+#[tauri::command]
+fn rebuild_link_index(link_index: State<'_, LinkIndexState>) -> Result<(), String> {
+    let fresh = LinkCache::build();
+    *link_index.0.lock().map_err(|e| e.to_string())? = fresh;
+    Ok(())
+}
+
+/// Filename for a note created from a link. Names with nothing left after
+/// conversion, like `..` or blanks, are rejected rather than becoming a
+/// hidden `.md` file.
+fn link_note_filename(note_name: &str, strategy: NamingStrategy) -> Result<String, String> {
+    let filename = note_name_to_filename_with(note_name, strategy);
+    let stem = filename.trim_end_matches(".md");
+    if stem.trim().is_empty() {
+        return Err(format!("'{}' can't be used as a note name", note_name));
+    }
+    // Slugs are already path-safe; verbatim names are checked like any other
+    sanitize_filename(stem)?;
+    Ok(filename)
 }
 
 #[tauri::command]
-fn create_note_from_link(note_name: String) -> Result<String, String> {
-    let filename = note_name_to_filename(&note_name);
+fn create_note_from_link(
+    note_name: String,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<String, String> {
+    let filename = link_note_filename(&note_name, settings::naming_strategy())?;
     let notes_dir = get_notes_dir();
     let notes_path = notes_dir.join("notes");
 
     std::fs::create_dir_all(&notes_path)
         .map_err(|e| format!("Failed to create notes directory: {}", e))?;
 
-    let file_path = notes_path.join(&filename);
-
-    // Check if file already exists
-    if file_path.exists() {
-        return Err(format!("Note '{}' already exists", filename));
+    // Check if file already exists (ignoring case)
+    if let Some(existing) = find_case_insensitive_conflict(&notes_path, &filename) {
+        return Err(format!("Note '{}' already exists", existing));
     }
 
+    let file_path = notes_path.join(&filename);
+
     // Create with a basic heading
     let initial_content = format!("# {}\n\n", note_name);
 
-    std::fs::write(&file_path, initial_content).map_err(|e| format!("Failed to create note: {}", e))?;
+    std::fs::write(&file_path, &initial_content)
+        .map_err(|e| format!("Failed to create note: {}", e))?;
+
+    link_index
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .update(&file_path, false, &initial_content);
 
     Ok(filename)
 }
 
+const MAX_LINK_SUGGESTIONS: usize = 20;
+
+#[tauri::command]
+fn suggest_links(prefix: String) -> Result<Vec<String>, String> {
+    let prefix = prefix.trim().to_lowercase();
+    let ignore_rules = load_ignore_rules();
+
+    // (rank, creation time, name) for every candidate display name
+    let mut candidates: Vec<(u8, SystemTime, String)> = Vec::new();
+
+    for path in list_note_paths(true)
+        .into_iter()
+        .chain(list_note_paths(false))
+    {
+        if is_ignored(&ignore_rules, &path) {
+            continue;
+        }
+
+        let created = fs::metadata(&path)
+            .and_then(|m| m.created().or_else(|_| m.modified()))
+            .unwrap_or(UNIX_EPOCH);
+
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        // Falls back to the stem, which the dedup below drops
+        let title = fs::read_to_string(&path)
+            .ok()
+            .map(|content| note_title(&stem, &content));
+
+        for name in std::iter::once(stem).chain(title) {
+            if let Some(rank) = suggestion_rank(&name, &prefix) {
+                candidates.push((rank, created, name));
+            }
+        }
+    }
+
+    // Best rank first; within a rank, most recently created notes first
+    candidates.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+
+    let mut seen = HashSet::new();
+    let suggestions = candidates
+        .into_iter()
+        .map(|(_, _, name)| name)
+        .filter(|name| !name.is_empty() && seen.insert(name.to_lowercase()))
+        .take(MAX_LINK_SUGGESTIONS)
+        .collect();
+
+    Ok(suggestions)
+}
+
 #[tauri::command]
 fn ensure_directories() -> Result<(), String> {
     let notes_dir = get_notes_dir();
@@ -392,188 +2040,1951 @@ fn ensure_directories() -> Result<(), String> {
 }
 
 #[tauri::command]
-fn list_notes() -> Result<Vec<NoteFile>, String> {
-    let mut notes = Vec::new();
+fn get_vault_path() -> Result<String, String> {
+    Ok(get_notes_dir().to_string_lossy().to_string())
+}
 
-    // List daily notes
-    let daily_dir = get_daily_dir();
-    if daily_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&daily_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "md") {
-                    let name = path.file_name().unwrap().to_string_lossy().to_string();
-                    let date = name.strip_suffix(".md").map(|s| s.to_string());
-                    notes.push(NoteFile {
-                        name: name.clone(),
-                        path: format!("daily/{}", name),
-                        is_daily: true,
-                        date,
-                    });
-                }
-            }
-        }
-    }
+#[tauri::command]
+fn set_vault_path(
+    app: AppHandle,
+    link_index: State<'_, LinkIndexState>,
+    path: String,
+) -> Result<(), String> {
+    settings::set_vault_path(&path)?;
+    ensure_directories()?;
 
-    // List standalone notes
-    let standalone_dir = get_standalone_dir();
-    if standalone_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&standalone_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "md") {
-                    let name = path.file_name().unwrap().to_string_lossy().to_string();
-                    notes.push(NoteFile {
-                        name: name.clone(),
-                        path: format!("notes/{}", name),
-                        is_daily: false,
-                        date: None,
-                    });
-                }
-            }
-        }
+    // Everything cached or watched belongs to the old vault
+    *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+    if let Err(e) = watcher::start(app) {
+        log::warn!("Failed to restart file watcher: {}", e);
     }
 
-    Ok(notes)
+    Ok(())
+}
+
+#[tauri::command]
+fn get_daily_folder_format() -> Option<String> {
+    settings::daily_folder_format()
+}
+
+/// Set the strftime pattern (e.g. `%Y/%m`) for the folders new daily notes
+/// are created in, or clear it to keep them at the top of the daily folder
+#[tauri::command]
+fn set_daily_folder_format(format: Option<String>) -> Result<(), String> {
+    settings::set_daily_folder_format(format.as_deref())
+}
+
+#[tauri::command]
+fn get_max_note_versions() -> usize {
+    settings::max_note_versions()
+}
+
+#[tauri::command]
+fn set_max_note_versions(count: usize) -> Result<(), String> {
+    settings::set_max_note_versions(count)
+}
+
+#[tauri::command]
+fn get_naming_strategy() -> NamingStrategy {
+    settings::naming_strategy()
+}
+
+/// Choose how note names from wiki links become filenames. Links to notes
+/// named under an earlier choice still resolve.
+#[tauri::command]
+fn set_naming_strategy(
+    strategy: NamingStrategy,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<(), String> {
+    settings::set_naming_strategy(strategy)?;
+    // Links to notes that don't exist yet now resolve to different filenames
+    *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+    Ok(())
+}
+
+#[tauri::command]
+fn get_default_template(is_daily: bool) -> Option<String> {
+    settings::default_template(is_daily)
+}
+
+/// Set the template new daily or standalone notes start from when created
+/// without one, or clear it so they start empty
+#[tauri::command]
+fn set_default_template(is_daily: bool, template_id: Option<String>) -> Result<(), String> {
+    let template_id = template_id.filter(|id| !id.trim().is_empty());
+    if let Some(id) = &template_id {
+        if get_template(id.clone())?.is_snippet {
+            return Err(format!("'{}' is a snippet, not a note template", id));
+        }
+    }
+    settings::set_default_template(is_daily, template_id.as_deref())
+}
+
+/// Gitignore-style patterns, relative to the vault root, for notes that
+/// listing, search, backlinks and the link graph skip
+#[tauri::command]
+fn get_ignore_patterns() -> Vec<String> {
+    read_ignore_patterns()
+}
+
+/// Save the ignore patterns to `.notomatticignore` and re-index links so
+/// newly ignored notes drop out of backlinks and the graph
+#[tauri::command]
+fn set_ignore_patterns(
+    patterns: Vec<String>,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<(), String> {
+    write_ignore_patterns(&patterns)?;
+    *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+    Ok(())
+}
+
+/// Modification and creation times of a file as ISO-8601 UTC strings. Either
+/// is `None` when the platform doesn't provide it (creation time is missing
+/// on some Linux filesystems).
+fn file_timestamps(path: &Path) -> (Option<String>, Option<String>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return (None, None);
+    };
+    let iso = |time: SystemTime| {
+        chrono::DateTime::<chrono::Utc>::from(time)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    };
+
+    (
+        metadata.modified().ok().map(iso),
+        metadata.created().ok().map(iso),
+    )
+}
+
+/// Collect every (non-ignored) daily and standalone note in directory order
+fn collect_notes() -> Result<Vec<NoteFile>, String> {
+    collect_notes_with(&[])
+}
+
+/// `collect_notes`, also listing files with any of `extra_extensions`
+fn collect_notes_with(extra_extensions: &[String]) -> Result<Vec<NoteFile>, String> {
+    let ignore_rules = load_ignore_rules();
+    let pinned: HashSet<String> = pins::load().into_iter().collect();
+    let mut notes = Vec::new();
+
+    // List daily notes, including any nested in dated folders
+    let notes_dir = get_notes_dir();
+    for path in list_note_paths_with(true, extra_extensions) {
+        if is_ignored(&ignore_rules, &path) {
+            continue;
+        }
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let relative = path
+            .strip_prefix(&notes_dir)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| format!("daily/{}", name));
+        let frontmatter = read_frontmatter(&path);
+        let date = note_date(&name, true, &frontmatter);
+        let (modified, created) = file_timestamps(&path);
+        notes.push(NoteFile {
+            name,
+            is_pinned: pinned.contains(&relative),
+            path: relative,
+            is_daily: true,
+            date,
+            title: frontmatter.title,
+            tags: frontmatter.tags,
+            preview: None,
+            modified,
+            created,
+        });
+    }
+
+    // List standalone notes
+    for path in list_note_paths_with(false, extra_extensions) {
+        if is_ignored(&ignore_rules, &path) {
+            continue;
+        }
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let frontmatter = read_frontmatter(&path);
+        let (modified, created) = file_timestamps(&path);
+        notes.push(NoteFile {
+            name: name.clone(),
+            path: format!("notes/{}", name),
+            is_daily: false,
+            date: note_date(&name, false, &frontmatter),
+            title: frontmatter.title,
+            tags: frontmatter.tags,
+            is_pinned: pinned.contains(&format!("notes/{}", name)),
+            preview: None,
+            modified,
+            created,
+        });
+    }
+
+    Ok(notes)
+}
+
+/// Modification time of a note, in seconds since the epoch
+fn note_modified_secs(path: &Path) -> i64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Modification time in milliseconds, finer than seconds so a save made
+/// elsewhere just after ours is still noticed
+fn note_modified_millis(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+}
+
+/// Creation time of a note, preferring a frontmatter `created` date over
+/// filesystem metadata (which isn't available everywhere)
+fn note_created_secs(path: &Path) -> i64 {
+    let from_frontmatter = read_frontmatter(path).created.and_then(|created| {
+        chrono::DateTime::parse_from_rfc3339(&created)
+            .map(|dt| dt.timestamp())
+            .ok()
+            .or_else(|| {
+                chrono::NaiveDateTime::parse_from_str(&created, "%Y-%m-%d %H:%M")
+                    .ok()
+                    .map(|dt| dt.and_utc().timestamp())
+            })
+            .or_else(|| {
+                chrono::NaiveDate::parse_from_str(&created, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|dt| dt.and_utc().timestamp())
+            })
+    });
+
+    from_frontmatter.unwrap_or_else(|| {
+        fs::metadata(path)
+            .and_then(|m| m.created().or_else(|_| m.modified()))
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs() as i64)
+    })
+}
+
+const PREVIEW_LENGTH: usize = 200;
+
+/// Plain-text start of a note's body, skipping frontmatter and the leading
+/// heading, cut at a word boundary near `PREVIEW_LENGTH` characters
+fn note_preview(content: &str) -> Option<String> {
+    let (_, body) = frontmatter::parse_frontmatter(content);
+    let body = body.trim_start();
+    let body = match body.strip_prefix("# ") {
+        Some(heading_line) => heading_line.split_once('\n').map_or("", |(_, rest)| rest),
+        None => body,
+    };
+
+    let text = stats::strip_markdown(body)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= PREVIEW_LENGTH {
+        return Some(text);
+    }
+
+    let cut: String = text.chars().take(PREVIEW_LENGTH).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) => &cut[..space],
+        None => cut.as_str(),
+    };
+    Some(format!("{}…", cut.trim_end()))
+}
+
+#[tauri::command]
+fn list_notes(
+    sort_by: Option<SortKey>,
+    descending: Option<bool>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    with_preview: Option<bool>,
+    include_extensions: Option<Vec<String>>,
+) -> Result<NotePage, String> {
+    // Other text files (`txt`, `canvas`, ...) are only listed when asked for
+    let extensions: Vec<String> = include_extensions
+        .unwrap_or_default()
+        .iter()
+        .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+    let mut notes = collect_notes_with(&extensions)?;
+    let notes_dir = get_notes_dir();
+
+    // Without a sort key, notes keep directory order (daily first)
+    if let Some(key) = sort_by {
+        match key {
+            SortKey::Name => notes.sort_by_key(|n| n.name.to_lowercase()),
+            SortKey::Modified => {
+                notes.sort_by_cached_key(|n| note_modified_secs(&notes_dir.join(&n.path)))
+            }
+            SortKey::Created => {
+                notes.sort_by_cached_key(|n| note_created_secs(&notes_dir.join(&n.path)))
+            }
+        }
+        if descending.unwrap_or(false) {
+            notes.reverse();
+        }
+    }
+
+    let total = notes.len();
+    let mut notes: Vec<NoteFile> = notes
+        .into_iter()
+        .skip(offset.unwrap_or(0))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    // Only the returned page is read, so previews stay cheap with pagination
+    if with_preview.unwrap_or(false) {
+        for note in &mut notes {
+            note.preview = fs::read_to_string(notes_dir.join(&note.path))
+                .ok()
+                .and_then(|content| note_preview(&content));
+        }
+    }
+
+    Ok(NotePage { notes, total })
+}
+
+/// The closest existing daily note before or after `date` (`YYYY-MM-DD`),
+/// skipping days without one, or `None` past the first or last note
+#[tauri::command]
+fn adjacent_daily_note(date: String, direction: Direction) -> Result<Option<NoteFile>, String> {
+    let parse = |date: &str| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    let from = parse(date.trim())
+        .ok_or_else(|| format!("Invalid date '{}', expected YYYY-MM-DD", date))?;
+
+    let dailies = collect_notes()?
+        .into_iter()
+        .filter(|note| note.is_daily)
+        .filter_map(|note| {
+            let day = note.date.as_deref().and_then(parse)?;
+            Some((day, note))
+        });
+
+    let adjacent = match direction {
+        Direction::Previous => dailies
+            .filter(|(day, _)| *day < from)
+            .max_by_key(|(day, _)| *day),
+        Direction::Next => dailies
+            .filter(|(day, _)| *day > from)
+            .min_by_key(|(day, _)| *day),
+    };
+
+    Ok(adjacent.map(|(_, note)| note))
+}
+
+/// Daily notes from today's month and day in earlier years, newest first
+#[tauri::command]
+fn notes_on_this_day() -> Result<Vec<NoteFile>, String> {
+    use chrono::Datelike;
+
+    let today = Local::now().date_naive();
+    let mut notes: Vec<(chrono::NaiveDate, NoteFile)> = collect_notes()?
+        .into_iter()
+        .filter(|note| note.is_daily)
+        .filter_map(|note| {
+            let day = chrono::NaiveDate::parse_from_str(note.date.as_deref()?, "%Y-%m-%d").ok()?;
+            Some((day, note))
+        })
+        .filter(|(day, _)| {
+            day.year() < today.year() && day.month() == today.month() && day.day() == today.day()
+        })
+        .collect();
+    notes.sort_by_key(|(day, _)| std::cmp::Reverse(*day));
+
+    Ok(notes.into_iter().map(|(_, note)| note).collect())
+}
+
+/// Word count of each daily note in `year`, by date, for a calendar heatmap.
+/// Days without a daily note are left out.
+#[tauri::command]
+fn daily_note_counts(year: i32) -> Result<Vec<DayCount>, String> {
+    use chrono::Datelike;
+
+    let ignore_rules = load_ignore_rules();
+    let mut counts: Vec<(chrono::NaiveDate, usize)> = Vec::new();
+
+    for path in list_note_paths(true) {
+        let Some(day) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|stem| chrono::NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok())
+        else {
+            continue;
+        };
+        if day.year() != year || is_ignored(&ignore_rules, &path) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let word_count = stats::compute(&content, &WordCountOptions::default()).word_count;
+        counts.push((day, word_count));
+    }
+    counts.sort_by_key(|(day, _)| *day);
+
+    Ok(counts
+        .into_iter()
+        .map(|(day, word_count)| DayCount {
+            date: day.format("%Y-%m-%d").to_string(),
+            word_count,
+        })
+        .collect())
+}
+
+/// Notes last modified on a local date from `start` to `end` (both
+/// `YYYY-MM-DD`, inclusive), most recently modified first
+#[tauri::command]
+fn notes_modified_between(start: String, end: String) -> Result<Vec<NoteFile>, String> {
+    let parse = |date: &str| {
+        chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", date))
+    };
+    let (start, end) = (parse(&start)?, parse(&end)?);
+    if start > end {
+        return Err("The start date is after the end date".to_string());
+    }
+
+    let notes_dir = get_notes_dir();
+    let mut notes: Vec<(i64, NoteFile)> = collect_notes()?
+        .into_iter()
+        .map(|note| (note_modified_secs(&notes_dir.join(&note.path)), note))
+        .filter(|(secs, _)| {
+            // 0 means the modified time couldn't be read
+            *secs != 0
+                && chrono::TimeZone::timestamp_opt(&Local, *secs, 0)
+                    .single()
+                    .map(|time| time.date_naive())
+                    .is_some_and(|date| date >= start && date <= end)
+        })
+        .collect();
+    notes.sort_by_key(|(secs, _)| std::cmp::Reverse(*secs));
+
+    Ok(notes.into_iter().map(|(_, note)| note).collect())
+}
+
+/// Join a caller-supplied filename onto a note folder, rejecting anything
+/// (`..`, absolute paths, symlinks) that would land outside that folder
+fn resolve_note_path(dir: &Path, filename: &str) -> Result<PathBuf, String> {
+    let invalid = || format!("Invalid note path: '{}'", filename);
+
+    if Path::new(filename).file_name() != Some(std::ffi::OsStr::new(filename)) {
+        return Err(invalid());
+    }
+
+    let path = dir.join(filename);
+    let Ok(canonical_dir) = dir.canonicalize() else {
+        // Nothing to escape into yet; the name itself was checked above
+        return Ok(path);
+    };
+
+    // The note may not exist yet, so check where its parent resolves to
+    let parent = path
+        .parent()
+        .and_then(|parent| parent.canonicalize().ok())
+        .ok_or_else(invalid)?;
+    if parent != canonical_dir {
+        return Err(invalid());
+    }
+
+    if let Ok(canonical) = path.canonicalize() {
+        if !canonical.starts_with(&canonical_dir) {
+            return Err(invalid());
+        }
+    }
+
+    Ok(path)
+}
+
+#[tauri::command]
+fn read_note(filename: String, is_daily: bool) -> Result<String, String> {
+    Ok(read_note_versioned(filename, is_daily)?.content)
+}
+
+/// `read_note` plus the modification time the content was read at
+#[tauri::command]
+fn read_note_versioned(filename: String, is_daily: bool) -> Result<VersionedNote, String> {
+    let dir = if is_daily {
+        get_daily_note_dir(&filename)
+    } else {
+        get_standalone_dir()
+    };
+
+    let path = resolve_note_path(&dir, &filename)?;
+
+    if path.exists() {
+        // Taken before reading, so a write landing mid-read shows as a change
+        let mtime = note_modified_millis(&path);
+        let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+        // Text files other than markdown can be read too, but not binaries
+        let content = String::from_utf8(bytes)
+            .ok()
+            .filter(|content| !content.contains('\0'))
+            .ok_or_else(|| format!("'{}' is a binary file, not a text note", filename))?;
+        recents::record(pins::note_key(&filename, is_daily));
+        Ok(VersionedNote { content, mtime })
+    } else {
+        Ok(VersionedNote {
+            content: String::new(),
+            mtime: None,
+        })
+    }
+}
+
+/// Validated path of a note that must already exist
+fn existing_note_path(filename: &str, is_daily: bool) -> Result<PathBuf, String> {
+    let dir = if is_daily {
+        get_daily_note_dir(filename)
+    } else {
+        get_standalone_dir()
+    };
+
+    let path = resolve_note_path(&dir, filename)?;
+    if !path.exists() {
+        return Err("Note not found".to_string());
+    }
+
+    // Absolute even when the vault setting is a relative path
+    path.canonicalize().map_err(|e| e.to_string())
+}
+
+/// Absolute path of a note on disk, e.g. for opening it in another editor
+#[tauri::command]
+fn get_note_path(filename: String, is_daily: bool) -> Result<String, String> {
+    Ok(existing_note_path(&filename, is_daily)?
+        .to_string_lossy()
+        .to_string())
+}
+
+/// Resolve a note name the way wiki links are resolved, without reading the note
+#[tauri::command]
+fn check_note_exists(note_name: String) -> Result<NoteExistence, String> {
+    let (exists, filename) = note_exists(&note_name)?;
+    let is_daily = exists && find_note_file(&filename).is_some_and(|(_, is_daily)| is_daily);
+
+    Ok(NoteExistence {
+        filename,
+        exists,
+        is_daily,
+    })
+}
+
+/// Show a note in the system file manager: selected in Finder or Explorer,
+/// or its folder opened on Linux, where file managers have no common way
+/// to select a file
+#[tauri::command]
+fn reveal_note_in_finder(app: AppHandle, filename: String, is_daily: bool) -> Result<(), String> {
+    let path = existing_note_path(&filename, is_daily)?;
+    let shell = app.shell();
+
+    #[cfg(target_os = "macos")]
+    let command = shell
+        .command("open")
+        .args(["-R".to_string(), path.to_string_lossy().to_string()]);
+    #[cfg(target_os = "windows")]
+    let command = shell
+        .command("explorer")
+        .args([format!("/select,{}", path.display())]);
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let command = {
+        let folder = path.parent().ok_or("Note has no parent folder")?;
+        shell
+            .command("xdg-open")
+            .args([folder.to_string_lossy().to_string()])
+    };
+
+    // Only a failure to launch is an error; Explorer exits non-zero even on success
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Could not open the file manager: {}", e))
+}
+
+/// Write a file by writing a temporary sibling and renaming it over the
+/// target, so a crash leaves either the old or the new content, never a mix
+fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // Not a `.md` file, so it never shows up as a note or in the watcher
+    let temp_path = path.with_file_name(format!(".{}.tmp", filename));
+
+    let result = fs::File::create(&temp_path).and_then(|mut file| {
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+
+    match result.and_then(|_| fs::rename(&temp_path, path)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+/// Convert CRLF line endings (from editing on Windows) to LF
+fn to_lf_line_endings(content: String) -> String {
+    if content.contains("\r\n") {
+        content.replace("\r\n", "\n")
+    } else {
+        content
+    }
+}
+
+/// Rewrite every note with CRLF line endings to use LF, returning how many
+/// files changed
+#[tauri::command]
+fn normalize_vault_line_endings(link_index: State<'_, LinkIndexState>) -> Result<usize, String> {
+    let notes_dir = get_notes_dir();
+    let mut changed = 0;
+
+    for note in collect_notes()? {
+        let path = notes_dir.join(&note.path);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if !content.contains("\r\n") {
+            continue;
+        }
+        write_atomic(&path, &to_lf_line_endings(content)).map_err(|e| e.to_string())?;
+        changed += 1;
+    }
+
+    if changed > 0 {
+        *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+    }
+
+    Ok(changed)
+}
+
+/// Bring the link index up to date with a note just written, which had
+/// `old_aliases` before the write
+fn reindex_written_note(
+    link_index: &LinkIndexState,
+    path: &Path,
+    is_daily: bool,
+    old_aliases: Option<Vec<String>>,
+    content: &str,
+) -> Result<(), String> {
+    let mut cache = link_index.0.lock().map_err(|e| e.to_string())?;
+    // Links elsewhere may now resolve (or stop resolving) through this note
+    let new_aliases = frontmatter::parse_frontmatter(content)
+        .0
+        .and_then(|fm| fm.aliases);
+    if new_aliases != old_aliases {
+        *cache = LinkCache::build();
+    } else {
+        cache.update(path, is_daily, content);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+// Each option is a separate argument so the frontend invokes it with named fields
+#[allow(clippy::too_many_arguments)]
+fn write_note(
+    app: AppHandle,
+    link_index: State<'_, LinkIndexState>,
+    filename: String,
+    content: String,
+    is_daily: bool,
+    snapshot: Option<bool>,
+    normalize_line_endings: Option<bool>,
+    expected_mtime: Option<u64>,
+) -> Result<Option<u64>, String> {
+    // On by default; opting out keeps CRLF for users who need it
+    let content = if normalize_line_endings.unwrap_or(true) {
+        to_lf_line_endings(content)
+    } else {
+        content
+    };
+    let dir = if is_daily {
+        get_daily_note_dir(&filename)
+    } else {
+        get_standalone_dir()
+    };
+    // A daily note's dated folder may not exist yet
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let path = resolve_note_path(&dir, &filename)?;
+
+    // Refuse to overwrite a change made elsewhere since the caller's read,
+    // including the note being deleted
+    if let Some(expected) = expected_mtime {
+        if note_modified_millis(&path) != Some(expected) {
+            return Err(format!(
+                "Conflict: '{}' was changed outside Notomattic",
+                filename
+            ));
+        }
+    }
+
+    let old_aliases = read_frontmatter(&path).aliases;
+    write_atomic(&path, &content).map_err(|e| e.to_string())?;
+
+    // A failed snapshot shouldn't fail the save itself
+    if snapshot.unwrap_or(false) {
+        if let Err(e) =
+            snapshots::snapshot(&filename, is_daily, &content, settings::max_note_versions())
+        {
+            log::warn!("Failed to snapshot note: {}", e);
+        }
+    }
+
+    reindex_written_note(&link_index, &path, is_daily, old_aliases, &content)?;
+
+    // Push fresh counts to the stats bar so it doesn't need a follow-up call
+    let note_stats = stats::compute(&content, &WordCountOptions::default());
+    let event = StatsUpdatedEvent {
+        filename,
+        is_daily,
+        word_count: note_stats.word_count,
+        char_count: note_stats.char_count,
+        line_count: note_stats.line_count,
+    };
+    if let Err(e) = app.emit("stats-updated", event) {
+        log::warn!("Failed to emit stats-updated event: {}", e);
+    }
+
+    Ok(note_modified_millis(&path))
+}
+
+/// Append text to a file, creating it if needed. A newline is inserted first
+/// when the existing content doesn't end with one.
+fn append_text(path: &Path, text: &str) -> std::io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)?;
+
+    let len = file.metadata()?.len();
+    let needs_newline = if len > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        last[0] != b'\n'
+    } else {
+        false
+    };
+
+    if needs_newline {
+        file.write_all(b"\n")?;
+    }
+    file.write_all(text.as_bytes())
+}
+
+#[tauri::command]
+fn append_to_note(
+    filename: String,
+    text: String,
+    is_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<(), String> {
+    let dir = if is_daily {
+        get_daily_note_dir(&filename)
+    } else {
+        get_standalone_dir()
+    };
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let path = resolve_note_path(&dir, &filename)?;
+    append_text(&path, &text).map_err(|e| e.to_string())?;
+
+    // The appended text may contain links, so re-index the note
+    if let Ok(content) = fs::read_to_string(&path) {
+        link_index
+            .0
+            .lock()
+            .map_err(|e| e.to_string())?
+            .update(&path, is_daily, &content);
+    }
+
+    Ok(())
+}
+
+/// Insert `text` at the end of the `## heading` section, before the next
+/// heading of the same or higher level. The heading is appended when missing.
+fn insert_under_heading(content: &str, heading: &str, text: &str) -> String {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let wanted = heading.trim();
+
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.strip_prefix("## ").is_some_and(|h| h.trim() == wanted))
+    else {
+        let mut result = content.trim_end().to_string();
+        if !result.is_empty() {
+            result.push_str("\n\n");
+        }
+        return format!("{}## {}\n{}\n", result, wanted, text);
+    };
+
+    let mut end = lines[start + 1..]
+        .iter()
+        .position(|line| line.starts_with("# ") || line.starts_with("## "))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+
+    // Keep the blank line separating this section from the next one
+    while end > start + 1 && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+
+    let separator = end < lines.len();
+    lines.insert(end, text);
+    if separator && !lines[end + 1].trim().is_empty() {
+        lines.insert(end + 1, "");
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+#[tauri::command]
+fn capture_to_daily(
+    text: String,
+    heading: Option<String>,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<(), String> {
+    let filename = create_daily_note(None)?;
+    let path = get_daily_note_dir(&filename).join(&filename);
+
+    match heading.filter(|h| !h.trim().is_empty()) {
+        Some(heading) => {
+            let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            let updated = insert_under_heading(&content, &heading, &text);
+            write_atomic(&path, &updated).map_err(|e| e.to_string())?;
+        }
+        None => append_text(&path, &text).map_err(|e| e.to_string())?,
+    }
+
+    if let Ok(content) = fs::read_to_string(&path) {
+        link_index
+            .0
+            .lock()
+            .map_err(|e| e.to_string())?
+            .update(&path, true, &content);
+    }
+
+    Ok(())
+}
+
+/// Which other notes link to a note, wiki and markdown links alike, so the
+/// frontend can warn before it's deleted
+#[tauri::command]
+fn get_delete_impact(
+    filename: String,
+    is_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<DeleteImpact, String> {
+    existing_note_path(&filename, is_daily)?;
+
+    let cache = link_index.0.lock().map_err(|e| e.to_string())?;
+    let linking_notes: Vec<NoteRef> = cache
+        .sources_linking_to(&filename, true)
+        .into_iter()
+        .filter(|source| source.filename != filename)
+        .map(|source| NoteRef {
+            filename: source.filename,
+            is_daily: source.is_daily,
+        })
+        .collect();
+
+    Ok(DeleteImpact {
+        count: linking_notes.len(),
+        linking_notes,
+    })
+}
+
+#[tauri::command]
+fn delete_note(
+    filename: String,
+    is_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<(), String> {
+    let dir = if is_daily {
+        get_daily_note_dir(&filename)
+    } else {
+        get_standalone_dir()
+    };
+
+    let path = resolve_note_path(&dir, &filename)?;
+
+    // Deleting a note that's already gone is not an error
+    if path.exists() {
+        // Soft-delete: notes go to the trash and can be restored
+        let trash_filename = trash::move_to_trash(&path, is_daily)?;
+
+        link_index
+            .0
+            .lock()
+            .map_err(|e| e.to_string())?
+            .remove(&filename, is_daily);
+
+        if let Err(e) = undo::record(UndoAction::Delete { trash_filename }) {
+            log::warn!("Failed to record undo entry: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Move several notes to the trash, carrying on past notes that fail. The
+/// whole batch is one undo step.
+#[tauri::command]
+fn delete_notes(
+    refs: Vec<NoteRef>,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<DeleteReport, String> {
+    let mut cache = link_index.0.lock().map_err(|e| e.to_string())?;
+    let mut results = Vec::new();
+    let mut trash_filenames = Vec::new();
+
+    for note in refs {
+        let dir = if note.is_daily {
+            get_daily_note_dir(&note.filename)
+        } else {
+            get_standalone_dir()
+        };
+
+        let outcome = resolve_note_path(&dir, &note.filename).and_then(|path| {
+            if !path.exists() {
+                return Err("Note not found".to_string());
+            }
+            trash::move_to_trash(&path, note.is_daily)
+        });
+
+        let error = match outcome {
+            Ok(trash_filename) => {
+                cache.remove(&note.filename, note.is_daily);
+                trash_filenames.push(trash_filename);
+                None
+            }
+            Err(e) => Some(e),
+        };
+
+        results.push(DeleteOutcome {
+            filename: note.filename,
+            is_daily: note.is_daily,
+            error,
+        });
+    }
+
+    let deleted = trash_filenames.len();
+    if deleted > 0 {
+        if let Err(e) = undo::record(UndoAction::DeleteMany { trash_filenames }) {
+            log::warn!("Failed to record undo entry: {}", e);
+        }
+    }
+
+    Ok(DeleteReport { results, deleted })
+}
+
+/// Characters that are path separators or that Windows forbids in filenames
+const FORBIDDEN_FILENAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Make a note title safe to use as a filename stem: forbidden characters
+/// become `-`, control characters are dropped, and surrounding dots and
+/// whitespace are trimmed so the name can't point outside its folder
+fn sanitize_filename(name: &str) -> Result<String, String> {
+    let cleaned: String = name
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| {
+            if FORBIDDEN_FILENAME_CHARS.contains(&c) {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').trim().to_string();
+
+    if cleaned.is_empty() {
+        return Err("Note name is empty after removing invalid characters".to_string());
+    }
+
+    // Guard against anything that would still resolve outside the folder
+    let mut components = Path::new(&cleaned).components();
+    match (components.next(), components.next()) {
+        (Some(std::path::Component::Normal(_)), None) => Ok(cleaned),
+        _ => Err(format!("Invalid note name: '{}'", name)),
+    }
+}
+
+/// Find an existing file in `dir` whose name matches `filename` ignoring
+/// case, since macOS and Windows treat `My Note.md` and `my note.md` as one
+fn find_case_insensitive_conflict(dir: &Path, filename: &str) -> Option<String> {
+    let wanted = filename.to_lowercase();
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .find(|name| name.to_lowercase() == wanted)
+}
+
+/// Create a standalone note from `template_id`, or from the default
+/// standalone template when none is given
+#[tauri::command]
+fn create_note(title: String, template_id: Option<String>) -> Result<String, String> {
+    let dir = get_standalone_dir();
+    let filename = format!("{}.md", sanitize_filename(&title)?);
+
+    if let Some(existing) = find_case_insensitive_conflict(&dir, &filename) {
+        return Err(format!(
+            "A note with this name already exists: '{}'",
+            existing
+        ));
+    }
+
+    let path = dir.join(&filename);
+    let content = new_note_content(template_id, false)?;
+
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(filename)
+}
+
+/// Point the first `# ` heading at a new title, leaving the rest untouched
+fn retitle_first_heading(content: &str, title: &str) -> String {
+    let mut replaced = false;
+    let lines: Vec<String> = content
+        .split('\n')
+        .map(|line| {
+            if !replaced && line.starts_with("# ") {
+                replaced = true;
+                format!("# {}", title)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[tauri::command]
+fn duplicate_note(
+    filename: String,
+    is_daily: bool,
+    new_title: String,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<String, String> {
+    let source_path = existing_note_path(&filename, is_daily)?;
+
+    // Copies are always standalone, named like create_note
+    let new_filename = format!("{}.md", sanitize_filename(&new_title)?);
+    let standalone_dir = get_standalone_dir();
+
+    if let Some(existing) = find_case_insensitive_conflict(&standalone_dir, &new_filename) {
+        return Err(format!(
+            "A note with this name already exists: '{}'",
+            existing
+        ));
+    }
+
+    let new_path = standalone_dir.join(&new_filename);
+
+    let content = fs::read_to_string(&source_path).map_err(|e| e.to_string())?;
+    let content = retitle_first_heading(&content, &new_title);
+
+    fs::write(&new_path, &content).map_err(|e| e.to_string())?;
+
+    link_index
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .update(&new_path, false, &content);
+
+    Ok(new_filename)
+}
+
+#[tauri::command]
+fn rename_note(
+    old_filename: String,
+    new_filename: String,
+    is_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<(), String> {
+    rename_note_file(&old_filename, &new_filename, is_daily, &link_index)?;
+
+    if let Err(e) = undo::record(UndoAction::Rename {
+        old_filename,
+        new_filename,
+        is_daily,
+        relinked: Vec::new(),
+    }) {
+        log::warn!("Failed to record undo entry: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Rename a note on disk and in the index and pins, without logging an undo
+/// entry, so callers that do more can log one covering everything
+fn rename_note_file(
+    old_filename: &str,
+    new_filename: &str,
+    is_daily: bool,
+    link_index: &LinkIndexState,
+) -> Result<(), String> {
+    // A renamed daily note moves to the dated folder of its new name
+    let (old_dir, new_dir) = if is_daily {
+        (
+            get_daily_note_dir(old_filename),
+            get_daily_note_dir(new_filename),
+        )
+    } else {
+        (get_standalone_dir(), get_standalone_dir())
+    };
+
+    // The frontend keeps using the name it asked for, so reject rather than
+    // silently sanitize. Existing names only need to stay inside the folder.
+    let new_stem = new_filename.strip_suffix(".md").unwrap_or(new_filename);
+    if sanitize_filename(new_stem)? != new_stem {
+        return Err(format!("Invalid note name: '{}'", new_filename));
+    }
+    if Path::new(old_filename).file_name() != Some(std::ffi::OsStr::new(old_filename)) {
+        return Err(format!("Invalid note name: '{}'", old_filename));
+    }
+
+    let old_path = old_dir.join(old_filename);
+    let new_path = new_dir.join(new_filename);
+
+    if !old_path.exists() {
+        return Err("Note not found".to_string());
+    }
+
+    if new_path.exists() {
+        return Err("A note with this name already exists".to_string());
+    }
+
+    // The pin key is the note's path, so it has to be read before the move
+    let old_key = pins::note_key(old_filename, is_daily);
+
+    fs::create_dir_all(&new_dir).map_err(|e| e.to_string())?;
+    fs::rename(&old_path, &new_path).map_err(|e| e.to_string())?;
+
+    {
+        let mut cache = link_index.0.lock().map_err(|e| e.to_string())?;
+        cache.remove(old_filename, is_daily);
+        if let Ok(content) = fs::read_to_string(&new_path) {
+            cache.update(&new_path, is_daily, &content);
+        }
+    }
+
+    if let Err(e) = pins::rename(&old_key, pins::note_key(new_filename, is_daily)) {
+        log::warn!("Failed to update pinned notes: {}", e);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+fn move_note(
+    filename: String,
+    from_daily: bool,
+    to_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<String, String> {
+    let to_dir = if to_daily {
+        get_daily_note_dir(&filename)
+    } else {
+        get_standalone_dir()
+    };
+
+    let old_key = pins::note_key(&filename, from_daily);
+    if from_daily == to_daily {
+        return Ok(old_key);
+    }
+
+    // Daily notes are keyed by date, so the filename has to be one
+    if to_daily {
+        let is_date = filename.strip_suffix(".md").is_some_and(|stem| {
+            stem.len() == 10 && chrono::NaiveDate::parse_from_str(stem, "%Y-%m-%d").is_ok()
+        });
+        if !is_date {
+            return Err("Daily notes must be named YYYY-MM-DD.md".to_string());
+        }
+    }
+
+    let old_path = existing_note_path(&filename, from_daily)?;
+    let new_path = resolve_note_path(&to_dir, &filename)?;
+
+    if new_path.exists() {
+        return Err("A note with this name already exists".to_string());
+    }
+
+    fs::create_dir_all(&to_dir).map_err(|e| e.to_string())?;
+    fs::rename(&old_path, &new_path).map_err(|e| e.to_string())?;
+
+    {
+        let mut cache = link_index.0.lock().map_err(|e| e.to_string())?;
+        cache.remove(&filename, from_daily);
+        if let Ok(content) = fs::read_to_string(&new_path) {
+            cache.update(&new_path, to_daily, &content);
+        }
+    }
+
+    let new_key = pins::note_key(&filename, to_daily);
+    if let Err(e) = pins::rename(&old_key, new_key.clone()) {
+        log::warn!("Failed to update pinned notes: {}", e);
+    }
+
+    if let Err(e) = undo::record(UndoAction::Move {
+        filename,
+        from_daily,
+        to_daily,
+    }) {
+        log::warn!("Failed to record undo entry: {}", e);
+    }
+
+    Ok(new_key)
+}
+
+#[tauri::command]
+fn rename_note_with_links(
+    old_filename: String,
+    new_filename: String,
+    is_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<RelinkReport, String> {
+    rename_note_file(&old_filename, &new_filename, is_daily, &link_index)?;
+
+    let notes_dir = get_notes_dir();
+    let new_target = new_filename.trim_end_matches(".md");
+    let mut relinked = Vec::new();
+    let mut failed = Vec::new();
+
+    // The rename has happened, so a note that can't be rewritten is
+    // reported rather than stopping the others
+    for note in collect_notes()? {
+        let path = notes_dir.join(&note.path);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        if let Some(rewritten) = rewrite_links_to(&content, &old_filename, new_target) {
+            if let Err(e) = write_atomic(&path, &rewritten) {
+                log::warn!("Failed to update links in '{}': {}", note.name, e);
+                failed.push(note.name);
+                continue;
+            }
+            link_index.0.lock().map_err(|e| e.to_string())?.update(
+                &path,
+                note.is_daily,
+                &rewritten,
+            );
+            relinked.push(RelinkedNote {
+                filename: note.name,
+                is_daily: note.is_daily,
+            });
+        }
+    }
+
+    let updated = relinked.len();
+    // One entry, so undoing puts the links back along with the name
+    if let Err(e) = undo::record(UndoAction::Rename {
+        old_filename,
+        new_filename,
+        is_daily,
+        relinked,
+    }) {
+        log::warn!("Failed to record undo entry: {}", e);
+    }
+
+    Ok(RelinkReport { updated, failed })
+}
+
+/// Replace every match of `find` in the given notes (the whole vault when
+/// `filenames` is omitted). `find` is literal text unless `use_regex` is set,
+/// in which case `replace` may use `$1`-style groups. With `dry_run` the
+/// counts are reported without writing anything.
+#[tauri::command]
+fn replace_in_notes(
+    find: String,
+    replace: String,
+    use_regex: bool,
+    filenames: Option<Vec<String>>,
+    dry_run: Option<bool>,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<ReplaceReport, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    if find.is_empty() {
+        return Err("Search text is empty".to_string());
+    }
+
+    let pattern = if use_regex {
+        Regex::new(&find).map_err(|e| format!("Invalid regex: {}", e))?
+    } else {
+        Regex::new(&regex::escape(&find)).map_err(|e| e.to_string())?
+    };
+
+    let mut notes = collect_notes()?;
+    if let Some(filenames) = &filenames {
+        // Check every requested note exists before touching any of them
+        if let Some(missing) = filenames
+            .iter()
+            .find(|f| !notes.iter().any(|n| n.name == **f || n.path == **f))
+        {
+            return Err(format!("Note not found: '{}'", missing));
+        }
+        notes.retain(|n| filenames.iter().any(|f| n.name == *f || n.path == *f));
+    }
+    notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let notes_dir = get_notes_dir();
+    let mut files = Vec::new();
+    let mut total = 0;
+
+    for note in notes {
+        let path = notes_dir.join(&note.path);
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let count = pattern.find_iter(&content).count();
+        if count == 0 {
+            continue;
+        }
+
+        if !dry_run {
+            let updated = if use_regex {
+                pattern.replace_all(&content, replace.as_str())
+            } else {
+                pattern.replace_all(&content, regex::NoExpand(&replace))
+            };
+            write_atomic(&path, &updated).map_err(|e| e.to_string())?;
+
+            link_index
+                .0
+                .lock()
+                .map_err(|e| e.to_string())?
+                .update(&path, note.is_daily, &updated);
+        }
+
+        total += count;
+        files.push(FileReplacement {
+            filename: note.name,
+            is_daily: note.is_daily,
+            count,
+        });
+    }
+
+    Ok(ReplaceReport {
+        files,
+        total,
+        dry_run,
+    })
+}
+
+/// Move every note in the scope (all notes when unset) to the trash,
+/// returning how many were cleared. The clear is one undo step.
+#[tauri::command]
+fn clear_all_notes(
+    scope: Option<ClearScope>,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<usize, String> {
+    let folders: &[bool] = match scope.unwrap_or(ClearScope::All) {
+        ClearScope::All => &[true, false],
+        ClearScope::Daily => &[true],
+        ClearScope::Standalone => &[false],
+    };
+    let ignore_rules = load_ignore_rules();
+    let mut cache = link_index.0.lock().map_err(|e| e.to_string())?;
+    let mut trash_filenames = Vec::new();
+
+    for &is_daily in folders {
+        for path in list_note_paths(is_daily) {
+            if is_ignored(&ignore_rules, &path) {
+                continue;
+            }
+            let filename = path.file_name().unwrap().to_string_lossy().to_string();
+            match trash::move_to_trash(&path, is_daily) {
+                Ok(trash_filename) => {
+                    cache.remove(&filename, is_daily);
+                    trash_filenames.push(trash_filename);
+                }
+                // Keep what was moved so far undoable rather than bailing out
+                Err(e) => log::warn!("Failed to clear '{}': {}", filename, e),
+            }
+        }
+    }
+
+    let cleared = trash_filenames.len();
+    if cleared > 0 {
+        if let Err(e) = undo::record(UndoAction::DeleteMany { trash_filenames }) {
+            log::warn!("Failed to record undo entry: {}", e);
+        }
+    }
+
+    Ok(cleared)
+}
+
+// Search Commands
+
+/// Compiled size limit for search patterns, so a pathological regex fails
+/// to compile instead of eating memory and time on every note
+const MAX_SEARCH_PATTERN_SIZE: usize = 1024 * 1024;
+
+/// Start a case-insensitive search of note titles and content whose results
+/// arrive as `search-hit` events tagged with `request_id`, followed by a
+/// `search-done` event with the total. With `regex` the query is a pattern
+/// such as `TODO\s*:` rather than literal text.
+#[tauri::command]
+fn search_notes_streaming(
+    app: AppHandle,
+    query: String,
+    request_id: String,
+    regex: Option<bool>,
+) -> Result<(), String> {
+    let use_regex = regex.unwrap_or(false);
+    let query = if use_regex {
+        query.as_str()
+    } else {
+        query.trim()
+    };
+    if query.is_empty() {
+        return Err("Search text is empty".to_string());
+    }
+
+    let source = if use_regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let pattern = RegexBuilder::new(&source)
+        .case_insensitive(true)
+        .size_limit(MAX_SEARCH_PATTERN_SIZE)
+        .dfa_size_limit(MAX_SEARCH_PATTERN_SIZE)
+        .build()
+        .map_err(|e| format!("Invalid regex: {}", e))?;
+    search::start(app, pattern, request_id)
+}
+
+/// Stop a search started with `search_notes_streaming`, e.g. because the
+/// query changed; it finishes with a cancelled `search-done` event
+#[tauri::command]
+fn cancel_search(request_id: String) -> Result<(), String> {
+    search::cancel(&request_id)
+}
+
+// Pinned Note Commands
+
+#[tauri::command]
+fn pin_note(filename: String, is_daily: bool) -> Result<(), String> {
+    existing_note_path(&filename, is_daily)?;
+
+    pins::pin(pins::note_key(&filename, is_daily))
+}
+
+#[tauri::command]
+fn unpin_note(filename: String, is_daily: bool) -> Result<(), String> {
+    pins::unpin(&pins::note_key(&filename, is_daily))
+}
+
+#[tauri::command]
+fn list_pinned() -> Result<Vec<NoteFile>, String> {
+    let mut notes: HashMap<String, NoteFile> = collect_notes()?
+        .into_iter()
+        .map(|note| (note.path.clone(), note))
+        .collect();
+
+    // Pin order is preserved; pins for notes that no longer exist are skipped
+    Ok(pins::load()
+        .into_iter()
+        .filter_map(|key| notes.remove(&key))
+        .collect())
+}
+
+/// Notes most recently opened with `read_note`, newest first. Notes that
+/// have since been deleted or renamed are skipped.
+#[tauri::command]
+fn list_recent_notes(limit: usize) -> Result<Vec<NoteFile>, String> {
+    let mut notes: HashMap<String, NoteFile> = collect_notes()?
+        .into_iter()
+        .map(|note| (note.path.clone(), note))
+        .collect();
+
+    Ok(recents::load()
+        .into_iter()
+        .filter_map(|entry| notes.remove(&entry.path))
+        .take(limit)
+        .collect())
+}
+
+// Trash Commands
+
+#[tauri::command]
+fn list_trash() -> Result<Vec<TrashEntry>, String> {
+    trash::list()
+}
+
+#[tauri::command]
+fn restore_note(
+    trash_filename: String,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<String, String> {
+    let filename = trash::restore(&trash_filename)?;
+    *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+    Ok(filename)
+}
+
+#[tauri::command]
+fn empty_trash() -> Result<(), String> {
+    trash::empty()
+}
+
+#[tauri::command]
+fn undo_last(link_index: State<'_, LinkIndexState>) -> Result<UndoResult, String> {
+    let result = undo::undo_last()?;
+    *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+    Ok(result)
+}
+
+/// Same as `undo_last`, for callers that only need to know it worked
+#[tauri::command]
+fn undo_last_action(link_index: State<'_, LinkIndexState>) -> Result<(), String> {
+    undo_last(link_index).map(|_| ())
+}
+
+// Attachment Commands
+
+/// Store pasted bytes (e.g. an image) in the assets folder, returning the
+/// markdown image to insert into the note
+#[tauri::command]
+fn save_attachment(
+    note_filename: String,
+    data: Vec<u8>,
+    extension: String,
+) -> Result<String, String> {
+    attachments::save(&note_filename, &data, &extension)
+}
+
+/// Asset files that no note, archived ones included, refers to
+#[tauri::command]
+fn list_orphan_attachments() -> Result<Vec<String>, String> {
+    // Every note counts here, even ignored ones, so nothing in use is flagged
+    let mut paths = list_note_paths(true);
+    paths.extend(list_note_paths(false));
+    paths.extend(archive::list().into_iter().map(|(path, _)| path));
+
+    Ok(attachments::orphans(&paths))
+}
+
+// Archive Commands
+
+#[tauri::command]
+fn archive_note(
+    filename: String,
+    is_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<String, String> {
+    let dir = if is_daily {
+        get_daily_note_dir(&filename)
+    } else {
+        get_standalone_dir()
+    };
+
+    let path = resolve_note_path(&dir, &filename)?;
+    if !path.exists() {
+        return Err("Note not found".to_string());
+    }
+
+    let archived_path = archive::archive(&path, is_daily)?;
+
+    link_index
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&filename, is_daily);
+
+    Ok(archived_path)
+}
+
+/// Move an archived note back to the daily or standalone folder it came from,
+/// returning its vault-relative path
+#[tauri::command]
+fn unarchive_note(
+    filename: String,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<String, String> {
+    let (path, is_daily) = archive::unarchive(&filename)?;
+
+    if let Ok(content) = fs::read_to_string(&path) {
+        link_index
+            .0
+            .lock()
+            .map_err(|e| e.to_string())?
+            .update(&path, is_daily, &content);
+    }
+
+    Ok(pins::note_key(&filename, is_daily))
+}
+
+/// Archived notes, which `list_notes` and the other scans leave out
+#[tauri::command]
+fn list_archived() -> Result<Vec<NoteFile>, String> {
+    let notes_dir = get_notes_dir();
+
+    let notes = archive::list()
+        .into_iter()
+        .map(|(path, is_daily)| {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let frontmatter = read_frontmatter(&path);
+            let (modified, created) = file_timestamps(&path);
+            NoteFile {
+                path: path
+                    .strip_prefix(&notes_dir)
+                    .map(|p| p.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_default(),
+                date: note_date(&name, is_daily, &frontmatter),
+                name,
+                is_daily,
+                title: frontmatter.title,
+                tags: frontmatter.tags,
+                is_pinned: false,
+                preview: None,
+                modified,
+                created,
+            }
+        })
+        .collect();
+
+    Ok(notes)
+}
+
+// Version History Commands
+
+/// Commit every change in the vault to its git repository, creating the
+/// repository on first use. Returns `None` when there was nothing to commit.
+#[tauri::command]
+fn commit_vault(message: Option<String>) -> Result<Option<String>, String> {
+    history::commit_vault(message.as_deref())
+}
+
+#[tauri::command]
+fn note_history(filename: String, is_daily: bool) -> Result<Vec<Commit>, String> {
+    if Path::new(&filename).file_name() != Some(std::ffi::OsStr::new(&filename)) {
+        return Err(format!("Invalid note name: '{}'", filename));
+    }
+
+    history::note_history(&pins::note_key(&filename, is_daily))
+}
+
+/// A note's content as of `commit`. Standalone notes are checked before
+/// daily ones, like `note_exists` does.
+#[tauri::command]
+fn get_note_at(filename: String, commit: String) -> Result<String, String> {
+    if Path::new(&filename).file_name() != Some(std::ffi::OsStr::new(&filename)) {
+        return Err(format!("Invalid note name: '{}'", filename));
+    }
+
+    let candidates = [
+        pins::note_key(&filename, false),
+        pins::note_key(&filename, true),
+        // Where a daily note lived before any daily folder format was set
+        format!("daily/{}", filename),
+    ];
+    history::note_at(&candidates, &commit)
+}
+
+#[tauri::command]
+fn list_note_versions(filename: String, is_daily: bool) -> Result<Vec<NoteVersion>, String> {
+    snapshots::list(&filename, is_daily)
+}
+
+/// Replace a note with one of its snapshots, returning the restored content.
+/// The current content is snapshotted first so the restore can be undone.
+#[tauri::command]
+fn restore_note_version(
+    filename: String,
+    timestamp: String,
+    is_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<String, String> {
+    let dir = if is_daily {
+        get_daily_note_dir(&filename)
+    } else {
+        get_standalone_dir()
+    };
+
+    let path = resolve_note_path(&dir, &filename)?;
+    let content = snapshots::read(&filename, is_daily, &timestamp)?;
+    if let Ok(current) = fs::read_to_string(&path) {
+        snapshots::snapshot(&filename, is_daily, &current, settings::max_note_versions())?;
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    write_atomic(&path, &content).map_err(|e| e.to_string())?;
+
+    // Aliases may differ between versions, so rebuild rather than update
+    *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+
+    Ok(content)
+}
+
+// Import Commands
+
+#[tauri::command]
+fn import_notes(
+    source_dir: String,
+    as_daily: bool,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<ImportReport, String> {
+    let report = import::import_folder(Path::new(&source_dir), as_daily)?;
+
+    // Imported notes may link to (and be linked from) anything, so rebuild
+    *link_index.0.lock().map_err(|e| e.to_string())? = LinkCache::build();
+
+    Ok(report)
+}
+
+// Export Commands
+
+#[tauri::command]
+fn export_note_html(filename: String, is_daily: bool) -> Result<String, String> {
+    let path = existing_note_path(&filename, is_daily)?;
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let (_, body) = frontmatter::parse_frontmatter(&content);
+    let title = note_title(&filename, &content);
+
+    Ok(export::render_document(&title, &body))
+}
+
+/// Render a note to a PDF file at `dest_path`. Wiki links are printed as
+/// plain text with numbered references at the end.
+#[tauri::command]
+fn export_note_pdf(filename: String, is_daily: bool, dest_path: String) -> Result<(), String> {
+    let path = existing_note_path(&filename, is_daily)?;
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let (_, body) = frontmatter::parse_frontmatter(&content);
+    let title = note_title(&filename, &content);
+
+    pdf::export(&title, &body, Path::new(&dest_path))
+}
+
+#[tauri::command]
+fn export_note_bundle(filename: String, is_daily: bool, depth: u32) -> Result<NoteBundle, String> {
+    let path = existing_note_path(&filename, is_daily)?;
+
+    export::build_bundle(path, depth)
+}
+
+/// Markdown list of links to a note's headings, for pasting at the top of
+/// long notes
+#[tauri::command]
+fn generate_toc(filename: String, is_daily: bool) -> Result<String, String> {
+    let path = existing_note_path(&filename, is_daily)?;
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let (_, body) = frontmatter::parse_frontmatter(&content);
+    Ok(export::table_of_contents(&body))
 }
 
+/// A note's headings with their 1-based line numbers, for an outline panel
 #[tauri::command]
-fn read_note(filename: String, is_daily: bool) -> Result<String, String> {
-    let dir = if is_daily {
-        get_daily_dir()
-    } else {
-        get_standalone_dir()
-    };
+fn get_note_outline(filename: String, is_daily: bool) -> Result<Vec<Heading>, String> {
+    let path = existing_note_path(&filename, is_daily)?;
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    Ok(export::outline(&content))
+}
 
-    let path = dir.join(&filename);
+// Statistics Commands
 
-    if path.exists() {
-        fs::read_to_string(&path).map_err(|e| e.to_string())
-    } else {
-        Ok(String::new())
-    }
+/// Stats for content straight from the editor, without reading or saving
+/// the note, so they can update as the user types
+#[tauri::command]
+fn analyze_content(content: String, options: Option<WordCountOptions>) -> ContentStats {
+    stats::analyze(&content, &options.unwrap_or_default())
 }
 
 #[tauri::command]
-fn write_note(filename: String, content: String, is_daily: bool) -> Result<(), String> {
-    let dir = if is_daily {
-        get_daily_dir()
-    } else {
-        get_standalone_dir()
-    };
+fn note_stats(
+    filename: String,
+    is_daily: bool,
+    options: Option<WordCountOptions>,
+) -> Result<NoteStats, String> {
+    let path = existing_note_path(&filename, is_daily)?;
 
-    let path = dir.join(&filename);
-    fs::write(&path, content).map_err(|e| e.to_string())
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    Ok(stats::compute(&content, &options.unwrap_or_default()))
 }
 
+/// Totals for the whole vault, reading one note at a time
 #[tauri::command]
-fn delete_note(filename: String, is_daily: bool) -> Result<(), String> {
-    let dir = if is_daily {
-        get_daily_dir()
-    } else {
-        get_standalone_dir()
-    };
-
-    let path = dir.join(&filename);
+fn vault_stats(app: AppHandle, options: Option<WordCountOptions>) -> Result<VaultStats, String> {
+    let options = options.unwrap_or_default();
+    let mut stats = VaultStats::default();
+    let mut tags: HashSet<String> = HashSet::new();
+
+    scan_vault(&app, "vault_stats", |note, content| {
+        let daily_date = note.date.as_deref().filter(|date| {
+            note.is_daily && chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+        });
+        stats.add_note(note.is_daily, daily_date, content, &options);
+        tags.extend(tags::extract_tags(content));
+    })?;
 
-    if path.exists() {
-        fs::remove_file(&path).map_err(|e| e.to_string())
-    } else {
-        Ok(())
-    }
+    stats.tag_count = tags.len();
+    Ok(stats)
 }
 
+// Tag Commands
+
 #[tauri::command]
-fn create_note(title: String) -> Result<String, String> {
-    let dir = get_standalone_dir();
-    let filename = format!("{}.md", title);
-    let path = dir.join(&filename);
+fn list_tags() -> Result<Vec<TagCount>, String> {
+    let notes_dir = get_notes_dir();
+    let mut counts: HashMap<String, usize> = HashMap::new();
 
-    if path.exists() {
-        return Err("A note with this name already exists".to_string());
+    for note in collect_notes()? {
+        let Ok(content) = fs::read_to_string(notes_dir.join(&note.path)) else {
+            continue;
+        };
+        for tag in tags::extract_tags(&content) {
+            *counts.entry(tag).or_insert(0) += 1;
+        }
     }
 
-    fs::write(&path, "").map_err(|e| e.to_string())?;
-    Ok(filename)
+    let mut tag_counts: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+
+    // Most used first, alphabetical within the same count
+    tag_counts.sort_by(|a, b| b.count.cmp(&a.count).then(a.tag.cmp(&b.tag)));
+
+    Ok(tag_counts)
 }
 
 #[tauri::command]
-fn rename_note(old_filename: String, new_filename: String, is_daily: bool) -> Result<(), String> {
-    let dir = if is_daily {
-        get_daily_dir()
-    } else {
-        get_standalone_dir()
-    };
+fn notes_by_tag(tag: String) -> Result<Vec<NoteFile>, String> {
+    let notes_dir = get_notes_dir();
+    let tag = tag.trim().trim_start_matches('#').to_lowercase();
+
+    let notes = collect_notes()?
+        .into_iter()
+        .filter(|note| {
+            fs::read_to_string(notes_dir.join(&note.path))
+                .map(|content| tags::extract_tags(&content).contains(&tag))
+                .unwrap_or(false)
+        })
+        .collect();
+
+    Ok(notes)
+}
 
-    let old_path = dir.join(&old_filename);
-    let new_path = dir.join(&new_filename);
+// Frontmatter Property Commands
 
-    if !old_path.exists() {
-        return Err("Note not found".to_string());
-    }
+#[tauri::command]
+fn get_frontmatter(filename: String, is_daily: bool) -> Result<Map<String, Value>, String> {
+    let path = existing_note_path(&filename, is_daily)?;
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    frontmatter::read_properties(&content)
+}
 
-    if new_path.exists() {
-        return Err("A note with this name already exists".to_string());
+#[tauri::command]
+fn set_frontmatter(
+    link_index: State<'_, LinkIndexState>,
+    filename: String,
+    is_daily: bool,
+    props: Map<String, Value>,
+    snapshot: Option<bool>,
+) -> Result<(), String> {
+    let path = existing_note_path(&filename, is_daily)?;
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let old_aliases = frontmatter::parse_frontmatter(&content)
+        .0
+        .and_then(|fm| fm.aliases);
+    let updated = frontmatter::merge_properties(&content, props)?;
+    write_atomic(&path, &updated).map_err(|e| e.to_string())?;
+
+    // As in `write_note`, a failed snapshot shouldn't fail the save itself
+    if snapshot.unwrap_or(false) {
+        if let Err(e) =
+            snapshots::snapshot(&filename, is_daily, &updated, settings::max_note_versions())
+        {
+            log::warn!("Failed to snapshot note: {}", e);
+        }
     }
 
-    fs::rename(&old_path, &new_path).map_err(|e| e.to_string())
+    reindex_written_note(&link_index, &path, is_daily, old_aliases, &updated)
 }
 
+// Template System Commands
+
+/// All templates, or only those in `category` (matched case-insensitively)
 #[tauri::command]
-fn clear_all_notes() -> Result<(), String> {
-    // Delete all files in daily directory
-    let daily_dir = get_daily_dir();
-    if daily_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&daily_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().map_or(false, |ext| ext == "md") {
-                    fs::remove_file(&path).map_err(|e| e.to_string())?;
-                }
+fn list_templates(category: Option<String>) -> Result<Vec<Template>, String> {
+    let mut templates = get_default_templates();
+
+    // Load custom templates from disk. Each id is listed once: defaults win
+    // over custom files, matching `get_template`, and otherwise the file
+    // named after the id wins.
+    let custom = read_custom_templates()?;
+    for conflict in find_template_conflicts(&custom) {
+        log::warn!(
+            "Template id '{}' is used by {}; only one is listed",
+            conflict.id,
+            if conflict.shadows_default {
+                format!("a default template and {}", conflict.files.join(", "))
+            } else {
+                conflict.files.join(", ")
             }
+        );
+    }
+    for (_, template) in custom {
+        if !templates.iter().any(|t| t.id == template.id) {
+            templates.push(template);
         }
     }
 
-    // Delete all files in standalone directory
-    let standalone_dir = get_standalone_dir();
-    if standalone_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&standalone_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().map_or(false, |ext| ext == "md") {
-                    fs::remove_file(&path).map_err(|e| e.to_string())?;
-                }
-            }
-        }
+    if let Some(category) = normalize_template_category(category) {
+        templates.retain(|t| {
+            t.category
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(&category))
+        });
     }
 
-    Ok(())
+    Ok(templates)
 }
 
-// Template System Commands
+/// Template ids that several files on disk (or a file and a default
+/// template) share, only one of which `list_templates` returns
+#[tauri::command]
+fn validate_templates() -> Result<Vec<TemplateConflict>, String> {
+    Ok(find_template_conflicts(&read_custom_templates()?))
+}
 
+/// Distinct template categories, sorted, for grouping the template picker
 #[tauri::command]
-fn list_templates() -> Result<Vec<Template>, String> {
-    let mut templates = get_default_templates();
+fn list_template_categories() -> Result<Vec<String>, String> {
+    let mut categories: Vec<String> = Vec::new();
 
-    // Load custom templates from disk
-    let templates_dir = get_templates_dir()?;
-    if templates_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&templates_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "json") {
-                    if let Ok(content) = fs::read_to_string(&path) {
-                        if let Ok(template) = serde_json::from_str::<Template>(&content) {
-                            templates.push(template);
-                        }
-                    }
-                }
+    for template in list_templates(None)? {
+        if let Some(category) = normalize_template_category(template.category) {
+            if !categories.iter().any(|c| c.eq_ignore_ascii_case(&category)) {
+                categories.push(category);
             }
         }
     }
 
-    Ok(templates)
+    categories.sort_by_key(|c| c.to_lowercase());
+    Ok(categories)
 }
 
 #[tauri::command]
@@ -601,13 +4012,20 @@ fn get_template(id: String) -> Result<Template, String> {
 fn save_template(input: SaveTemplateInput) -> Result<Template, String> {
     ensure_templates_dir()?;
 
+    if !input.allow_custom_variables {
+        validate_template_variables(&input.content)?;
+    }
+
     let id = generate_template_id(&input.name);
     let templates_dir = get_templates_dir()?;
     let template_path = templates_dir.join(format!("{}.json", id));
 
     // Check if template with this ID already exists
     if template_path.exists() {
-        return Err(format!("A template with the name '{}' already exists", input.name));
+        return Err(format!(
+            "A template with the name '{}' already exists",
+            input.name
+        ));
     }
 
     // Check if trying to overwrite a default template
@@ -623,6 +4041,8 @@ fn save_template(input: SaveTemplateInput) -> Result<Template, String> {
         icon: input.icon,
         is_default: false,
         content: input.content,
+        category: normalize_template_category(input.category),
+        is_snippet: input.is_snippet,
     };
 
     let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
@@ -631,6 +4051,24 @@ fn save_template(input: SaveTemplateInput) -> Result<Template, String> {
     Ok(template)
 }
 
+/// Copy a template (default or custom) into a new custom template named
+/// `new_name`, so defaults can be customized without losing the original
+#[tauri::command]
+fn duplicate_template(id: String, new_name: String) -> Result<Template, String> {
+    let source = get_template(id)?;
+
+    save_template(SaveTemplateInput {
+        name: new_name,
+        description: source.description,
+        icon: source.icon,
+        content: source.content,
+        category: source.category,
+        is_snippet: source.is_snippet,
+        // The source was already accepted, custom variables and all
+        allow_custom_variables: true,
+    })
+}
+
 #[tauri::command]
 fn update_template(id: String, input: SaveTemplateInput) -> Result<Template, String> {
     // Check if trying to update a default template
@@ -646,6 +4084,10 @@ fn update_template(id: String, input: SaveTemplateInput) -> Result<Template, Str
         return Err(format!("Template '{}' not found", id));
     }
 
+    if !input.allow_custom_variables {
+        validate_template_variables(&input.content)?;
+    }
+
     let template = Template {
         id: id.clone(),
         name: input.name,
@@ -653,6 +4095,8 @@ fn update_template(id: String, input: SaveTemplateInput) -> Result<Template, Str
         icon: input.icon,
         is_default: false,
         content: input.content,
+        category: normalize_template_category(input.category),
+        is_snippet: input.is_snippet,
     };
 
     let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
@@ -661,6 +4105,13 @@ fn update_template(id: String, input: SaveTemplateInput) -> Result<Template, Str
     Ok(template)
 }
 
+#[tauri::command]
+fn list_template_variables() -> Vec<String> {
+    let mut names: Vec<String> = builtin_template_variables().into_keys().collect();
+    names.sort();
+    names
+}
+
 #[tauri::command]
 fn delete_template(id: String) -> Result<(), String> {
     // Check if trying to delete a default template
@@ -679,13 +4130,102 @@ fn delete_template(id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Write every custom template to a single JSON file, returning how many
+/// were exported. Default templates are built in, so they're left out.
+#[tauri::command]
+fn export_templates(dest_path: String) -> Result<usize, String> {
+    let templates: Vec<Template> = list_templates(None)?
+        .into_iter()
+        .filter(|t| !t.is_default)
+        .collect();
+    let count = templates.len();
+
+    let json =
+        serde_json::to_string_pretty(&TemplateBundle { templates }).map_err(|e| e.to_string())?;
+    fs::write(&dest_path, json).map_err(|e| e.to_string())?;
+
+    Ok(count)
+}
+
+/// Load templates from a file written by `export_templates`. Templates whose
+/// id is already taken, by a custom or default template, are skipped and
+/// listed in the report rather than overwritten.
+#[tauri::command]
+fn import_templates(source_path: String) -> Result<ImportReport, String> {
+    let content = fs::read_to_string(&source_path).map_err(|e| e.to_string())?;
+    let bundle: TemplateBundle =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid template file: {}", e))?;
+
+    ensure_templates_dir()?;
+    let templates_dir = get_templates_dir()?;
+    let defaults = get_default_templates();
+
+    let mut report = ImportReport {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        renamed: Vec::new(),
+    };
+
+    for mut template in bundle.templates {
+        // Ids become filenames, so only accept ones this app could have generated
+        if template.id.is_empty() || generate_template_id(&template.id) != template.id {
+            report.skipped.push(template.id);
+            continue;
+        }
+
+        let template_path = templates_dir.join(format!("{}.json", template.id));
+        if template_path.exists() || defaults.iter().any(|t| t.id == template.id) {
+            report.skipped.push(template.id);
+            continue;
+        }
+
+        template.is_default = false;
+        template.category = normalize_template_category(template.category);
+
+        let json = serde_json::to_string_pretty(&template).map_err(|e| e.to_string())?;
+        fs::write(&template_path, json).map_err(|e| e.to_string())?;
+        report.imported.push(template.id);
+    }
+
+    Ok(report)
+}
+
 #[tauri::command]
-fn apply_template(template_id: String) -> Result<String, String> {
+fn apply_template(
+    template_id: String,
+    variables: Option<HashMap<String, String>>,
+) -> Result<String, String> {
     let template = get_template(template_id)?;
-    let content = replace_template_variables(template.content);
+    let content = replace_template_variables_with(template.content, &variables.unwrap_or_default());
     Ok(content)
 }
 
+/// A snippet template's content with variables filled in, for inserting
+/// mid-note: any frontmatter is dropped, as is the trailing newline
+#[tauri::command]
+fn render_snippet(
+    template_id: String,
+    variables: Option<HashMap<String, String>>,
+) -> Result<String, String> {
+    let content = apply_template(template_id, variables)?;
+    let body = frontmatter::split(&content).map_or(content.as_str(), |(_, body)| body);
+    Ok(body.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Distinct `{{variable}}` names in a template, in the order they first
+/// appear, so custom ones can be asked for before `apply_template`
+#[tauri::command]
+fn template_variables_used(template_id: String) -> Result<Vec<String>, String> {
+    let template = get_template(template_id)?;
+    let mut seen = HashSet::new();
+    let names = TEMPLATE_VARIABLE_REGEX
+        .captures_iter(&template.content)
+        .map(|cap| cap[1].to_string())
+        .filter(|name| seen.insert(name.clone()))
+        .collect();
+    Ok(names)
+}
+
 #[tauri::command]
 fn create_note_from_template(
     filename: String,
@@ -693,7 +4233,7 @@ fn create_note_from_template(
     is_daily: bool,
 ) -> Result<(), String> {
     let dir = if is_daily {
-        get_daily_dir()
+        get_daily_note_dir(&filename)
     } else {
         get_standalone_dir()
     };
@@ -707,32 +4247,122 @@ fn create_note_from_template(
     let template = get_template(template_id)?;
     let content = replace_template_variables(template.content);
 
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     fs::write(&path, content).map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
-// Apple Calendar (EventKit) Commands - macOS only
+/// Starting content for a new note: the given template, else the default
+/// template set for daily or standalone notes, else nothing. A default that
+/// has since been deleted is skipped rather than blocking note creation.
+fn new_note_content(template_id: Option<String>, is_daily: bool) -> Result<String, String> {
+    if let Some(id) = template_id {
+        return Ok(replace_template_variables(get_template(id)?.content));
+    }
 
-#[cfg(target_os = "macos")]
+    let Some(id) = settings::default_template(is_daily) else {
+        return Ok(String::new());
+    };
+    match get_template(id.clone()) {
+        Ok(template) => Ok(replace_template_variables(template.content)),
+        Err(e) => {
+            log::warn!("Default template '{}' is unavailable: {}", id, e);
+            Ok(String::new())
+        }
+    }
+}
+
+/// Create today's daily note from `template_id`, or from the default daily
+/// template when none is given
 #[tauri::command]
-fn get_calendar_permission() -> CalendarPermission {
-    calendar::get_permission_status()
+fn create_daily_note(template_id: Option<String>) -> Result<String, String> {
+    let filename = format!("{}.md", Local::now().format("%Y-%m-%d"));
+    let dir = get_daily_note_dir(&filename);
+    let path = dir.join(&filename);
+
+    // Idempotent: an existing daily note is returned as-is
+    if path.exists() {
+        return Ok(filename);
+    }
+
+    let content = new_note_content(template_id, true)?;
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+
+    Ok(filename)
 }
 
-#[cfg(target_os = "macos")]
+/// Create the note for the current week (`2024-W23.md`) or month
+/// (`2024-06.md`) in the periodic folder if it doesn't exist yet, returning
+/// its filename. Templates can use `{{week_start}}`/`{{week_end}}` and
+/// `{{month_start}}`/`{{month_end}}`.
 #[tauri::command]
-fn request_calendar_permission() -> bool {
-    calendar::request_permission()
+fn create_periodic_note(period: Period, template_id: Option<String>) -> Result<String, String> {
+    let filename = periodic::filename(period, Local::now().date_naive());
+    let dir = periodic::get_periodic_dir();
+    let path = dir.join(&filename);
+
+    // Idempotent, like create_daily_note
+    if path.exists() {
+        return Ok(filename);
+    }
+
+    let content = match template_id {
+        Some(id) => replace_template_variables(get_template(id)?.content),
+        None => String::new(),
+    };
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+
+    Ok(filename)
+}
+
+// Calendar Commands (EventKit on macOS, an .ics file elsewhere)
+
+/// The cached permission status, checking with the system on first use
+fn cached_calendar_permission(state: &CalendarPermissionState) -> CalendarPermission {
+    match state.0.lock() {
+        Ok(mut cached) => *cached.get_or_insert_with(calendar::get_permission_status),
+        Err(_) => calendar::get_permission_status(),
+    }
+}
+
+fn forget_calendar_permission(state: &CalendarPermissionState) {
+    if let Ok(mut cached) = state.0.lock() {
+        *cached = None;
+    }
 }
 
-#[cfg(target_os = "macos")]
 #[tauri::command]
-fn is_calendar_authorized() -> bool {
-    calendar::is_authorized()
+fn get_calendar_permission(permission: State<'_, CalendarPermissionState>) -> CalendarPermission {
+    cached_calendar_permission(&permission)
+}
+
+#[tauri::command]
+fn request_calendar_permission(permission: State<'_, CalendarPermissionState>) -> bool {
+    let granted = calendar::request_permission();
+    forget_calendar_permission(&permission);
+    granted
+}
+
+#[tauri::command]
+fn is_calendar_authorized(permission: State<'_, CalendarPermissionState>) -> bool {
+    cached_calendar_permission(&permission).is_authorized()
+}
+
+/// Check the permission status again, e.g. after the user changed it in
+/// System Settings
+#[tauri::command]
+fn refresh_calendar_permission(
+    permission: State<'_, CalendarPermissionState>,
+) -> CalendarPermission {
+    forget_calendar_permission(&permission);
+    cached_calendar_permission(&permission)
 }
 
-#[cfg(target_os = "macos")]
 #[tauri::command]
 fn fetch_calendar_events(
     start_date: String,
@@ -742,16 +4372,164 @@ fn fetch_calendar_events(
     calendar::get_events(&start_date, &end_date, calendar_id.as_deref())
 }
 
-#[cfg(target_os = "macos")]
+/// Events on one day (`YYYY-MM-DD`, local midnight to midnight), in start
+/// order with all-day events first
+#[tauri::command]
+fn fetch_events_for_day(
+    date: String,
+    calendar_id: Option<String>,
+) -> Result<Vec<CalendarEvent>, String> {
+    chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|_| "Date must be in YYYY-MM-DD format".to_string())?;
+
+    let mut events = calendar::get_events(&date, &date, calendar_id.as_deref())?;
+    // Compared as instants, since events may carry different UTC offsets
+    events.sort_by_key(|event| {
+        let start = chrono::DateTime::parse_from_rfc3339(&event.start).ok();
+        (!event.is_all_day, start)
+    });
+    Ok(events)
+}
+
 #[tauri::command]
 fn list_calendars() -> Result<Vec<CalendarInfo>, String> {
     calendar::get_calendars()
 }
 
+const SCHEDULE_HEADING: &str = "Schedule";
+
+/// An event's title, linked to its URL and followed by its location when
+/// the event has them
+fn schedule_entry(event: &CalendarEvent) -> String {
+    let mut entry = if event.url.is_empty() {
+        event.title.clone()
+    } else {
+        format!("[{}]({})", event.title, event.url)
+    };
+    let location = event.location.trim();
+    if !location.is_empty() {
+        // Multi-line addresses are kept on the event's line
+        entry.push_str(" @ ");
+        entry.push_str(
+            &location
+                .lines()
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+    }
+    entry
+}
+
+/// One `- 09:00–09:30 Title @ Location` line per event, all-day events first
+fn format_schedule(events: &[CalendarEvent]) -> String {
+    let local_time = |iso: &str| {
+        chrono::DateTime::parse_from_rfc3339(iso)
+            .map(|dt| dt.with_timezone(&Local).format("%H:%M").to_string())
+            .unwrap_or_default()
+    };
+
+    let mut lines: Vec<String> = events
+        .iter()
+        .filter(|event| event.is_all_day)
+        .map(|event| format!("- All day: {}", schedule_entry(event)))
+        .collect();
+    lines.extend(
+        events
+            .iter()
+            .filter(|event| !event.is_all_day)
+            .map(|event| {
+                format!(
+                    "- {}–{} {}",
+                    local_time(&event.start),
+                    local_time(&event.end),
+                    schedule_entry(event)
+                )
+            }),
+    );
+
+    if lines.is_empty() {
+        lines.push("- No events".to_string());
+    }
+    lines.join("\n")
+}
+
+#[tauri::command]
+fn insert_events_into_daily(
+    date: String,
+    link_index: State<'_, LinkIndexState>,
+) -> Result<(), String> {
+    chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|_| "Date must be in YYYY-MM-DD format".to_string())?;
+
+    let filename = format!("{}.md", date);
+    let dir = get_daily_note_dir(&filename);
+    let path = dir.join(&filename);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+
+    // Running this twice for the same day shouldn't add a second schedule
+    let heading_line = format!("## {}", SCHEDULE_HEADING);
+    if content.lines().any(|line| line.trim_end() == heading_line) {
+        return Ok(());
+    }
+
+    let events = calendar::get_events(&date, &date, None)?;
+    let updated = insert_under_heading(&content, SCHEDULE_HEADING, &format_schedule(&events));
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    write_atomic(&path, &updated).map_err(|e| e.to_string())?;
+
+    link_index
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .update(&path, true, &updated);
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn get_ics_path() -> Option<String> {
+    settings::ics_path().map(|path| path.to_string_lossy().to_string())
+}
+
+// macOS reads Apple Calendar instead, but keeps the commands so the
+// frontend can call them on every platform
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn get_ics_path() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn set_ics_path(_path: String) -> Result<(), String> {
+    Err("Calendar files are only used on Linux and Windows".to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn set_ics_path(
+    path: String,
+    permission: State<'_, CalendarPermissionState>,
+) -> Result<(), String> {
+    settings::set_ics_path(&path)?;
+    // Access depends on the file, so a new path means a new status
+    forget_calendar_permission(&permission);
+    Ok(())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // The vault location must be known before the link index is built
+    settings::init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(LinkIndexState(Mutex::new(LinkCache::build())))
+        .manage(CalendarPermissionState(Mutex::new(None)))
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -760,41 +4538,324 @@ pub fn run() {
                         .build(),
                 )?;
             }
+
+            // Keep the UI in sync with edits made by other apps
+            if let Err(e) = watcher::start(app.handle().clone()) {
+                log::warn!("Failed to start file watcher: {}", e);
+            }
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             ensure_directories,
             list_notes,
+            notes_modified_between,
+            adjacent_daily_note,
+            notes_on_this_day,
+            daily_note_counts,
             read_note,
+            read_note_versioned,
+            get_note_path,
+            check_note_exists,
+            reveal_note_in_finder,
             write_note,
+            normalize_vault_line_endings,
+            append_to_note,
+            capture_to_daily,
+            get_delete_impact,
             delete_note,
+            delete_notes,
             create_note,
+            duplicate_note,
             rename_note,
+            rename_note_with_links,
+            replace_in_notes,
+            search_notes_streaming,
+            cancel_search,
+            move_note,
             clear_all_notes,
+            undo_last,
+            undo_last_action,
+            // Settings commands
+            get_vault_path,
+            set_vault_path,
+            get_daily_folder_format,
+            set_daily_folder_format,
+            get_max_note_versions,
+            set_max_note_versions,
+            get_naming_strategy,
+            set_naming_strategy,
+            get_default_template,
+            set_default_template,
+            get_ignore_patterns,
+            set_ignore_patterns,
+            // Pinned note commands
+            pin_note,
+            unpin_note,
+            list_pinned,
+            list_recent_notes,
+            // Trash commands
+            list_trash,
+            restore_note,
+            empty_trash,
+            // Attachment commands
+            save_attachment,
+            list_orphan_attachments,
+            // Archive commands
+            archive_note,
+            unarchive_note,
+            list_archived,
+            // Version history commands
+            commit_vault,
+            note_history,
+            get_note_at,
+            list_note_versions,
+            restore_note_version,
+            // Import commands
+            import_notes,
+            // Export commands
+            export_note_html,
+            export_note_bundle,
+            export_note_pdf,
+            generate_toc,
+            get_note_outline,
+            // Statistics commands
+            note_stats,
+            analyze_content,
+            vault_stats,
+            // Tag commands
+            list_tags,
+            notes_by_tag,
+            // Frontmatter property commands
+            get_frontmatter,
+            set_frontmatter,
             // Template system commands
             list_templates,
+            list_template_categories,
+            validate_templates,
             get_template,
             save_template,
+            duplicate_template,
             update_template,
             delete_template,
+            export_templates,
+            import_templates,
+            list_template_variables,
             apply_template,
+            render_snippet,
+            template_variables_used,
             create_note_from_template,
+            create_daily_note,
+            create_periodic_note,
             // Wiki Link system commands
             scan_note_links,
+            scan_note_links_with_index,
+            get_outgoing_links,
+            resolve_embeds,
             get_backlinks,
+            count_backlinks,
+            rebuild_link_index,
+            find_orphans,
+            find_broken_links,
+            find_duplicate_notes,
+            get_link_graph,
             create_note_from_link,
-            // Apple Calendar (EventKit) commands - macOS only
-            #[cfg(target_os = "macos")]
+            suggest_links,
+            // Calendar commands (EventKit on macOS, an .ics file elsewhere)
             get_calendar_permission,
-            #[cfg(target_os = "macos")]
             request_calendar_permission,
-            #[cfg(target_os = "macos")]
             is_calendar_authorized,
-            #[cfg(target_os = "macos")]
+            refresh_calendar_permission,
             fetch_calendar_events,
-            #[cfg(target_os = "macos")]
-            list_calendars
+            fetch_events_for_day,
+            list_calendars,
+            insert_events_into_daily,
+            get_ics_path,
+            set_ics_path
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty folder under the system temp dir, unique to the test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("notomattic-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolve_note_path_rejects_paths_outside_the_folder() {
+        let dir = temp_dir("resolve-note-path");
+        for filename in ["../x.md", "/etc/passwd", "a/b.md", "..", ""] {
+            assert!(
+                resolve_note_path(&dir, filename).is_err(),
+                "'{}' should be rejected",
+                filename
+            );
+        }
+        assert_eq!(
+            resolve_note_path(&dir, "note.md").unwrap(),
+            dir.join("note.md")
+        );
+    }
+
+    #[test]
+    fn ignore_rules_exclude_folders_and_honour_negations() {
+        let vault = temp_dir("ignore-rules");
+        let notes = vault.join("notes");
+        fs::create_dir_all(notes.join("drafts")).unwrap();
+        fs::write(
+            vault.join(IGNORE_FILENAME),
+            "drafts/\nsecret-*.md\n!secret-keep.md\n",
+        )
+        .unwrap();
+        for name in [
+            "drafts/idea.md",
+            "secret-one.md",
+            "secret-keep.md",
+            "plain.md",
+        ] {
+            fs::write(notes.join(name), "").unwrap();
+        }
+
+        let rules = load_ignore_rules_in(&vault);
+        assert!(is_ignored(&rules, &notes.join("drafts")));
+        assert!(is_ignored(&rules, &notes.join("drafts/idea.md")));
+        assert!(is_ignored(&rules, &notes.join("secret-one.md")));
+        assert!(!is_ignored(&rules, &notes.join("secret-keep.md")));
+        assert!(!is_ignored(&rules, &notes.join("plain.md")));
+    }
+
+    #[test]
+    fn ignore_rules_are_empty_without_an_ignore_file() {
+        let vault = temp_dir("ignore-rules-none");
+        let rules = load_ignore_rules_in(&vault);
+        assert!(!is_ignored(&rules, &vault.join("notes/plain.md")));
+    }
+
+    #[test]
+    fn slugs_drop_accents_and_keep_ascii_names_unchanged() {
+        let slug = |name| note_name_to_filename_with(name, NamingStrategy::Slug);
+        assert_eq!(slug("Café"), "cafe.md");
+        assert_eq!(slug("España"), "espana.md");
+        assert_eq!(slug("Über Notes"), "uber-notes.md");
+        assert_eq!(slug("Meeting Notes"), "meeting-notes.md");
+        assert_eq!(slug("cafe"), "cafe.md");
+    }
+
+    #[test]
+    fn link_context_around_emoji_and_cjk_text_keeps_whole_characters() {
+        let content = format!("{}[[x]]{}", "😀".repeat(30), "漢".repeat(40));
+        let context = get_link_context(&content, "x", 10);
+        assert_eq!(
+            context,
+            format!("...{}[[x]]{}...", "😀".repeat(10), "漢".repeat(10))
+        );
+    }
+
+    #[test]
+    fn link_context_does_not_split_multibyte_characters_at_the_window_edge() {
+        // 51 two-byte characters put a byte-based 50 window mid-character
+        let content = format!("{}[[x|y]]", "é".repeat(51));
+        let context = get_link_context(&content, "y", 50);
+        assert_eq!(context, format!("...{}[[x|y]]", "é".repeat(50)));
+    }
+
+    #[test]
+    fn each_naming_strategy_maps_names_to_filenames() {
+        let cases = [
+            (NamingStrategy::Slug, "c-notes.md", "meeting-notes.md"),
+            (NamingStrategy::Verbatim, "C++ Notes.md", "Meeting Notes.md"),
+            (
+                NamingStrategy::Underscore,
+                "C++_Notes.md",
+                "Meeting_Notes.md",
+            ),
+        ];
+        for (strategy, cpp, meeting) in cases {
+            assert_eq!(note_name_to_filename_with("C++ Notes", strategy), cpp);
+            assert_eq!(
+                note_name_to_filename_with("Meeting Notes", strategy),
+                meeting
+            );
+            assert!(!note_name_to_filename_with("a/b:c", strategy).contains('/'));
+        }
+    }
+
+    #[test]
+    fn link_note_filename_rejects_names_with_an_empty_stem() {
+        for strategy in NamingStrategy::ALL {
+            for name in ["..", "   ", ""] {
+                assert!(
+                    link_note_filename(name, strategy).is_err(),
+                    "'{}' should be rejected",
+                    name
+                );
+            }
+            assert!(link_note_filename("C++ Notes", strategy).is_ok());
+        }
+    }
+
+    #[test]
+    fn a_source_linking_twice_is_listed_once_with_the_first_link_context() {
+        let content = "see [[target]] first\n\nand [[Display|target]] again\n";
+        let path = temp_dir("backlinks-twice").join("source.md");
+        fs::write(&path, content).unwrap();
+        let links: Vec<(String, String)> = parse_wiki_links(content)
+            .into_iter()
+            .map(|link| (link, "target.md".to_string()))
+            .collect();
+        assert_eq!(links.len(), 2);
+        let source = link_index::SourceEntry {
+            filename: "source.md".to_string(),
+            is_daily: false,
+            title: "source".to_string(),
+            links,
+            markdown_links: Vec::new(),
+        };
+
+        let linking = linking_sources(vec![source], "target.md", false);
+        assert_eq!(linking.len(), 1);
+        assert_eq!(linking[0].source.filename, "source.md");
+
+        let found = find_link_in_file(&path, linking[0].wiki_link.as_deref(), None, 50)
+            .unwrap()
+            .unwrap();
+        assert!(found.context.contains("first"));
+        assert!(!found.context.contains("again"));
+    }
+
+    #[test]
+    fn find_wiki_link_matches_plain_piped_and_heading_links() {
+        let content = "intro [[Display|target]] outro";
+        assert_eq!(find_wiki_link(content, "target"), Some((6, 24)));
+        assert_eq!(find_wiki_link(content, "Display"), None);
+        assert_eq!(find_wiki_link("see [[target]]", "target"), Some((4, 14)));
+        assert_eq!(
+            find_wiki_link("see [[target#Plans]]", "target"),
+            Some((4, 20))
+        );
+        assert_eq!(
+            get_link_context(content, "target", 50),
+            "intro [[Display|target]] outro"
+        );
+    }
+
+    #[test]
+    fn find_link_in_file_reports_the_section_of_a_piped_link() {
+        let path = temp_dir("find-link-in-file").join("source.md");
+        fs::write(&path, "# Title\n\n## Plans\nsee [[Display|target]] here\n").unwrap();
+
+        let found = find_link_in_file(&path, Some("target"), None, 50)
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.section.as_deref(), Some("Plans"));
+        assert!(found.context.contains("[[Display|target]]"));
+    }
+}
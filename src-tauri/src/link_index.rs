@@ -0,0 +1,176 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Cached link data for a single source note
+#[derive(Debug, Clone)]
+pub struct SourceEntry {
+    pub filename: String,
+    pub is_daily: bool,
+    pub title: String,
+    /// Raw link texts paired with the filename each one resolved to
+    pub links: Vec<(String, String)>,
+    /// Markdown links to local notes, as note names paired with the
+    /// filename each one resolved to
+    pub markdown_links: Vec<(String, String)>,
+}
+
+/// In-memory map of which notes link to which, so backlink lookups don't
+/// have to rescan the whole vault
+#[derive(Debug, Default)]
+pub struct LinkCache {
+    /// Keyed by vault-relative path (`daily/…` or `notes/…`)
+    sources: HashMap<String, SourceEntry>,
+    /// Target filename -> relative paths of the notes linking to it
+    incoming: HashMap<String, BTreeSet<String>>,
+    /// Same as `incoming`, for markdown links; these only count as links
+    /// when a caller opts in
+    incoming_markdown: HashMap<String, BTreeSet<String>>,
+}
+
+/// Tauri-managed wrapper around the cache
+pub struct LinkIndexState(pub Mutex<LinkCache>);
+
+fn relative_path(filename: &str, is_daily: bool) -> String {
+    let folder = if is_daily { "daily" } else { "notes" };
+    format!("{}/{}", folder, filename)
+}
+
+impl LinkCache {
+    /// Scan every note in the vault and build a fresh index
+    pub fn build() -> Self {
+        // Full rebuilds follow changes aliases may depend on (ignore
+        // patterns, bulk edits, alias changes), so reread those too
+        crate::aliases::invalidate();
+        let mut cache = LinkCache::default();
+        let ignore_rules = crate::load_ignore_rules();
+
+        for is_daily in [true, false] {
+            for path in crate::list_note_paths(is_daily) {
+                if crate::is_ignored(&ignore_rules, &path) {
+                    continue;
+                }
+
+                if let Ok(content) = fs::read_to_string(&path) {
+                    cache.index_note(&path, is_daily, &content);
+                }
+            }
+        }
+
+        cache
+    }
+
+    /// Re-index a single note from its current content
+    pub fn update(&mut self, path: &Path, is_daily: bool, content: &str) {
+        // A note added with aliases (created, copied, restored, edited
+        // elsewhere) changes what they resolve to
+        let declares_aliases = crate::frontmatter::parse_frontmatter(content)
+            .0
+            .and_then(|fm| fm.aliases)
+            .is_some_and(|aliases| !aliases.is_empty());
+        if declares_aliases {
+            crate::aliases::invalidate();
+        }
+        self.index_note(path, is_daily, content);
+    }
+
+    fn index_note(&mut self, path: &Path, is_daily: bool, content: &str) {
+        let filename = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        let key = relative_path(&filename, is_daily);
+
+        self.remove_key(&key);
+
+        let resolve = |link: String| {
+            let (_, target) = crate::note_exists(&link).unwrap_or((false, String::new()));
+            (link, target)
+        };
+        let links: Vec<(String, String)> = crate::parse_wiki_links(content)
+            .into_iter()
+            .map(resolve)
+            .collect();
+        let markdown_links: Vec<(String, String)> = crate::parse_markdown_note_links(content)
+            .into_iter()
+            .map(|(name, _)| resolve(name))
+            .collect();
+
+        let indexes = [
+            (&mut self.incoming, &links),
+            (&mut self.incoming_markdown, &markdown_links),
+        ];
+        for (incoming, links) in indexes {
+            for (link, target) in links {
+                // Index under the raw name too, so `[[My Note]]` finds `My Note.md`
+                for name in [target.clone(), format!("{}.md", link)] {
+                    incoming.entry(name).or_default().insert(key.clone());
+                }
+            }
+        }
+
+        let title = crate::note_title(&filename, content);
+
+        self.sources.insert(
+            key,
+            SourceEntry {
+                filename,
+                is_daily,
+                title,
+                links,
+                markdown_links,
+            },
+        );
+    }
+
+    /// Drop a note from the index (after delete or rename)
+    pub fn remove(&mut self, filename: &str, is_daily: bool) {
+        // Any aliases the note declared go with it
+        crate::aliases::invalidate();
+        self.remove_key(&relative_path(filename, is_daily));
+    }
+
+    fn remove_key(&mut self, key: &str) {
+        if self.sources.remove(key).is_some() {
+            for incoming in [&mut self.incoming, &mut self.incoming_markdown] {
+                incoming.retain(|_, sources| {
+                    sources.remove(key);
+                    !sources.is_empty()
+                });
+            }
+        }
+    }
+
+    /// Relative paths of the notes linking to `filename`, in vault order
+    fn keys_linking_to(&self, filename: &str, include_markdown: bool) -> BTreeSet<&String> {
+        let mut keys: BTreeSet<&String> =
+            self.incoming.get(filename).into_iter().flatten().collect();
+        if include_markdown {
+            keys.extend(self.incoming_markdown.get(filename).into_iter().flatten());
+        }
+        keys
+    }
+
+    /// Notes linking to `filename`, in vault order (daily before standalone).
+    /// Markdown links only count when `include_markdown` is set.
+    pub fn sources_linking_to(&self, filename: &str, include_markdown: bool) -> Vec<SourceEntry> {
+        self.keys_linking_to(filename, include_markdown)
+            .into_iter()
+            .filter_map(|key| self.sources.get(key).cloned())
+            .collect()
+    }
+
+    /// Number of other notes linking to `filename`, without cloning their entries
+    pub fn count_linking_to(&self, filename: &str, include_markdown: bool) -> usize {
+        self.keys_linking_to(filename, include_markdown)
+            .into_iter()
+            .filter(|key| {
+                self.sources
+                    .get(key.as_str())
+                    .is_some_and(|source| source.filename != filename)
+            })
+            .count()
+    }
+}
@@ -0,0 +1,460 @@
+use lazy_static::lazy_static;
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+// A4 portrait, in millimetres
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+
+const BODY_SIZE: f32 = 11.0;
+const CODE_SIZE: f32 = 9.5;
+const LIST_INDENT: f32 = 6.0;
+const PT_TO_MM: f32 = 0.3528;
+
+lazy_static! {
+    static ref HTML_TAG_REGEX: Regex = Regex::new(r"<[^>]+>").unwrap();
+    // Tags after which HTML content continues on a new line
+    static ref HTML_BREAK_REGEX: Regex =
+        Regex::new(r"(?i)</(p|h[1-6]|li|div|blockquote|pre|tr)>|<br\s*/?>").unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FontStyle {
+    Regular,
+    Bold,
+    Italic,
+    Mono,
+}
+
+/// A piece of text and the font it's set in. Runs are glued together until a
+/// `" "` run (a point where the line may wrap) or a `"\n"` run (a line break).
+type Run = (String, FontStyle);
+
+/// Replace `[[wiki links]]` with their display text and a numbered marker,
+/// since links can't be followed in print. Returns the rewritten markdown
+/// and the referenced note for each marker.
+fn wiki_links_to_references(markdown: &str) -> (String, Vec<String>) {
+    let mut references: Vec<String> = Vec::new();
+
+    let rewritten = crate::WIKI_LINK_REGEX
+        .replace_all(markdown, |cap: &regex::Captures| {
+            let display = cap.get(1).map(|m| m.as_str().trim()).unwrap_or_default();
+            let raw_target = cap.get(2).map(|m| m.as_str()).unwrap_or(display);
+            let note_name = raw_target.split('#').next().unwrap_or(raw_target).trim();
+
+            let reference = match crate::note_exists(note_name) {
+                Ok((true, filename)) => filename.trim_end_matches(".md").to_string(),
+                _ => format!("{} (missing note)", note_name),
+            };
+            let number = match references.iter().position(|r| *r == reference) {
+                Some(index) => index + 1,
+                None => {
+                    references.push(reference);
+                    references.len()
+                }
+            };
+
+            format!("{} \\[{}\\]", display, number)
+        })
+        .into_owned();
+
+    (rewritten, references)
+}
+
+/// Approximate the printed width of text; the built-in fonts don't expose
+/// metrics here, so this uses average glyph widths
+fn text_width(text: &str, style: FontStyle, size: f32) -> f32 {
+    let average = if style == FontStyle::Mono { 0.6 } else { 0.52 };
+    text.chars().count() as f32 * average * size * PT_TO_MM
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+struct PdfWriter {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    fonts: [IndirectFontRef; 4],
+    /// Baseline of the next line, measured from the bottom of the page
+    y: f32,
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> Result<Self, String> {
+        let (doc, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+        let font = |builtin| doc.add_builtin_font(builtin).map_err(|e| e.to_string());
+        let fonts = [
+            font(BuiltinFont::Helvetica)?,
+            font(BuiltinFont::HelveticaBold)?,
+            font(BuiltinFont::HelveticaOblique)?,
+            font(BuiltinFont::Courier)?,
+        ];
+        let layer = doc.get_page(page).get_layer(layer);
+
+        Ok(PdfWriter {
+            doc,
+            layer,
+            fonts,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn font(&self, style: FontStyle) -> &IndirectFontRef {
+        match style {
+            FontStyle::Regular => &self.fonts[0],
+            FontStyle::Bold => &self.fonts[1],
+            FontStyle::Italic => &self.fonts[2],
+            FontStyle::Mono => &self.fonts[3],
+        }
+    }
+
+    /// Move down by `height`, starting a new page when it wouldn't fit
+    fn advance(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let (page, layer) = self
+                .doc
+                .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Content");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+        self.y -= height;
+    }
+
+    fn gap(&mut self, height: f32) {
+        // Gaps at the top of a page are dropped rather than carried over
+        if self.y - height >= MARGIN {
+            self.y -= height;
+        }
+    }
+
+    /// Lay out runs as word-wrapped lines. `prefix` (a bullet or number) is
+    /// set in the indent before the first line.
+    fn block(&mut self, runs: &[Run], size: f32, indent: f32, prefix: Option<&str>) {
+        let line_height = size * PT_TO_MM * 1.45;
+        let left = MARGIN + indent;
+        let width = PAGE_WIDTH - MARGIN - left;
+        let space = text_width(" ", FontStyle::Regular, size);
+        let run_width = |run: &Run| text_width(&run.0, run.1, size);
+
+        // Each line is a list of words, each word a list of glued runs
+        let mut lines: Vec<Vec<Vec<Run>>> = vec![Vec::new()];
+        let mut word: Vec<Run> = Vec::new();
+        let mut line_width = 0.0;
+
+        let place = |lines: &mut Vec<Vec<Vec<Run>>>, word: &mut Vec<Run>, line_width: &mut f32| {
+            if word.is_empty() {
+                return;
+            }
+            let word_width: f32 = word.iter().map(run_width).sum();
+            let current = lines.last_mut().unwrap();
+            if !current.is_empty() && *line_width + space + word_width > width {
+                lines.push(vec![std::mem::take(word)]);
+                *line_width = word_width;
+            } else {
+                if !current.is_empty() {
+                    *line_width += space;
+                }
+                current.push(std::mem::take(word));
+                *line_width += word_width;
+            }
+        };
+
+        for run in runs {
+            match run.0.as_str() {
+                " " => place(&mut lines, &mut word, &mut line_width),
+                "\n" => {
+                    place(&mut lines, &mut word, &mut line_width);
+                    lines.push(Vec::new());
+                    line_width = 0.0;
+                }
+                _ => word.push(run.clone()),
+            }
+        }
+        place(&mut lines, &mut word, &mut line_width);
+
+        for (i, line) in lines.iter().enumerate() {
+            self.advance(line_height);
+            if i == 0 {
+                if let Some(prefix) = prefix {
+                    let x = left - text_width(prefix, FontStyle::Regular, size) - 1.5;
+                    self.layer.use_text(
+                        prefix,
+                        size,
+                        Mm(x),
+                        Mm(self.y),
+                        self.font(FontStyle::Regular),
+                    );
+                }
+            }
+
+            let mut x = left;
+            for word in line {
+                for (text, style) in word {
+                    self.layer
+                        .use_text(text.as_str(), size, Mm(x), Mm(self.y), self.font(*style));
+                    x += text_width(text, *style, size);
+                }
+                x += space;
+            }
+        }
+    }
+
+    /// Code is printed line for line in a monospace font, hard-wrapped when
+    /// a line is wider than the page
+    fn code_block(&mut self, code: &str, indent: f32) {
+        let line_height = CODE_SIZE * PT_TO_MM * 1.35;
+        let left = MARGIN + indent + 3.0;
+        let per_line =
+            ((PAGE_WIDTH - MARGIN - left) / text_width("m", FontStyle::Mono, CODE_SIZE)) as usize;
+
+        for line in code.trim_end_matches('\n').lines() {
+            let chars: Vec<char> = line.replace('\t', "    ").chars().collect();
+            let chunks: Vec<String> = if chars.is_empty() {
+                vec![String::new()]
+            } else {
+                chars
+                    .chunks(per_line.max(1))
+                    .map(|c| c.iter().collect())
+                    .collect()
+            };
+            for chunk in chunks {
+                self.advance(line_height);
+                self.layer.use_text(
+                    chunk,
+                    CODE_SIZE,
+                    Mm(left),
+                    Mm(self.y),
+                    self.font(FontStyle::Mono),
+                );
+            }
+        }
+    }
+
+    fn save(self, dest: &Path) -> Result<(), String> {
+        let file = File::create(dest).map_err(|e| e.to_string())?;
+        self.doc
+            .save(&mut BufWriter::new(file))
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn heading_size(level: HeadingLevel) -> f32 {
+    match level {
+        HeadingLevel::H1 => 20.0,
+        HeadingLevel::H2 => 16.0,
+        HeadingLevel::H3 => 13.5,
+        _ => 12.0,
+    }
+}
+
+/// Add text, keeping word boundaries. Text events can split mid-word (e.g.
+/// around escaped characters), so pieces are only separated where the
+/// source had whitespace.
+fn push_words(runs: &mut Vec<Run>, text: &str, style: FontStyle) {
+    for (i, word) in text.split(char::is_whitespace).enumerate() {
+        if i > 0 {
+            push_space(runs);
+        }
+        if !word.is_empty() {
+            runs.push((word.to_string(), style));
+        }
+    }
+}
+
+fn push_space(runs: &mut Vec<Run>) {
+    if runs
+        .last()
+        .is_some_and(|(text, _)| text != " " && text != "\n")
+    {
+        runs.push((" ".to_string(), FontStyle::Regular));
+    }
+}
+
+fn push_line_break(runs: &mut Vec<Run>) {
+    if runs.last().is_some_and(|(text, _)| text != "\n") {
+        runs.push(("\n".to_string(), FontStyle::Regular));
+    }
+}
+
+/// Print the text of an HTML fragment, keeping its block-level line breaks
+fn push_html(runs: &mut Vec<Run>, html: &str, style: FontStyle) {
+    let with_breaks = HTML_BREAK_REGEX.replace_all(html, "\n");
+    for (i, line) in with_breaks.split('\n').enumerate() {
+        if i > 0 {
+            push_line_break(runs);
+        }
+        let text = HTML_TAG_REGEX.replace_all(line, " ");
+        push_words(runs, &decode_entities(&text), style);
+    }
+}
+
+/// Render a note's markdown to a PDF at `dest`. Wiki links become plain text
+/// with numbered references listed at the end. Only characters in the
+/// Windows-1252 set can be shown by the built-in PDF fonts.
+pub fn export(title: &str, markdown: &str, dest: &Path) -> Result<(), String> {
+    let (markdown, references) = wiki_links_to_references(markdown);
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut writer = PdfWriter::new(title)?;
+    let mut runs: Vec<Run> = Vec::new();
+    let mut bold = 0;
+    let mut italic = 0;
+    let mut indent = 0.0;
+    // The next number of each open list (None for bullet lists)
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut prefix: Option<String> = None;
+    let mut code: Option<String> = None;
+
+    let style = |bold: i32, italic: i32| {
+        if bold > 0 {
+            FontStyle::Bold
+        } else if italic > 0 {
+            FontStyle::Italic
+        } else {
+            FontStyle::Regular
+        }
+    };
+
+    // Text collected so far belongs to the enclosing block
+    let flush =
+        |writer: &mut PdfWriter, runs: &mut Vec<Run>, prefix: &mut Option<String>, indent: f32| {
+            if !runs.is_empty() {
+                writer.block(runs, BODY_SIZE, indent, prefix.as_deref());
+                writer.gap(1.5);
+                runs.clear();
+                *prefix = None;
+            }
+        };
+
+    for event in Parser::new_ext(&markdown, options) {
+        match event {
+            Event::Start(Tag::Heading { .. }) | Event::Start(Tag::Paragraph) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+            }
+            Event::End(TagEnd::Heading(level)) => {
+                writer.gap(3.0);
+                writer.block(&runs, heading_size(level), indent, None);
+                writer.gap(2.0);
+                runs.clear();
+            }
+            Event::End(TagEnd::Paragraph)
+            | Event::End(TagEnd::Item)
+            | Event::End(TagEnd::TableRow)
+            | Event::End(TagEnd::HtmlBlock) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+            }
+            Event::Start(Tag::TableHead) => bold += 1,
+            Event::End(TagEnd::TableHead) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                bold -= 1;
+            }
+            Event::Start(Tag::List(start)) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                lists.push(start);
+                indent += LIST_INDENT;
+            }
+            Event::End(TagEnd::List(_)) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                lists.pop();
+                indent -= LIST_INDENT;
+            }
+            Event::Start(Tag::Item) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                prefix = Some(match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "-".to_string(),
+                });
+            }
+            Event::Start(Tag::BlockQuote(_)) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                indent += LIST_INDENT;
+                italic += 1;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                indent -= LIST_INDENT;
+                italic -= 1;
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                code = Some(String::new());
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some(code) = code.take() {
+                    writer.code_block(&code, indent);
+                    writer.gap(2.0);
+                }
+            }
+            Event::Start(Tag::Strong) => bold += 1,
+            Event::End(TagEnd::Strong) => bold -= 1,
+            Event::Start(Tag::Emphasis) => italic += 1,
+            Event::End(TagEnd::Emphasis) => italic -= 1,
+            // Cells are separated with bars, rows end up one per line
+            Event::Start(Tag::TableCell) if !runs.is_empty() => {
+                push_words(&mut runs, " | ", FontStyle::Regular);
+            }
+            Event::Text(text) => match code.as_mut() {
+                Some(code) => code.push_str(&text),
+                None => push_words(&mut runs, &text, style(bold, italic)),
+            },
+            Event::Code(text) => push_words(&mut runs, &text, FontStyle::Mono),
+            // Notes saved by the editor may contain HTML, so print its text
+            Event::Html(html) | Event::InlineHtml(html) => {
+                push_html(&mut runs, &html, style(bold, italic));
+            }
+            Event::TaskListMarker(checked) => {
+                prefix = Some(if checked { "[x]" } else { "[ ]" }.to_string());
+            }
+            Event::SoftBreak => push_space(&mut runs),
+            Event::HardBreak => push_line_break(&mut runs),
+            Event::Rule => {
+                flush(&mut writer, &mut runs, &mut prefix, indent);
+                writer.gap(4.0);
+            }
+            _ => {}
+        }
+    }
+
+    if !runs.is_empty() {
+        writer.block(&runs, BODY_SIZE, indent, prefix.as_deref());
+    }
+
+    if !references.is_empty() {
+        writer.gap(6.0);
+        writer.block(
+            &[("References".to_string(), FontStyle::Bold)],
+            12.0,
+            0.0,
+            None,
+        );
+        writer.gap(1.5);
+        for (i, reference) in references.iter().enumerate() {
+            let mut runs = Vec::new();
+            push_words(&mut runs, reference, FontStyle::Regular);
+            writer.block(&runs, BODY_SIZE, LIST_INDENT, Some(&format!("[{}]", i + 1)));
+        }
+    }
+
+    writer.save(dest)
+}
@@ -0,0 +1,42 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub enum Period {
+    Weekly,
+    Monthly,
+}
+
+pub fn get_periodic_dir() -> PathBuf {
+    crate::get_notes_dir().join("periodic")
+}
+
+/// `2024-W23.md` (ISO week, so the year is the week's, not always the
+/// date's) or `2024-06.md`
+pub fn filename(period: Period, date: NaiveDate) -> String {
+    match period {
+        Period::Weekly => format!("{}.md", date.format("%G-W%V")),
+        Period::Monthly => format!("{}.md", date.format("%Y-%m")),
+    }
+}
+
+/// First and last day of the period containing `date`; weeks run Monday to Sunday
+pub fn bounds(period: Period, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match period {
+        Period::Weekly => {
+            let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            (start, start + Duration::days(6))
+        }
+        Period::Monthly => {
+            let start = date.with_day(1).unwrap_or(date);
+            let next_month = if start.month() == 12 {
+                NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(start.year(), start.month() + 1, 1)
+            };
+            let end = next_month.map(|d| d - Duration::days(1)).unwrap_or(date);
+            (start, end)
+        }
+    }
+}
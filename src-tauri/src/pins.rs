@@ -0,0 +1,65 @@
+use std::fs;
+use std::path::PathBuf;
+
+const PINNED_FILENAME: &str = "pinned.json";
+
+fn get_pinned_path() -> PathBuf {
+    crate::get_notes_dir().join(PINNED_FILENAME)
+}
+
+/// Vault-relative note path, matching `NoteFile.path`
+pub fn note_key(filename: &str, is_daily: bool) -> String {
+    if !is_daily {
+        return format!("notes/{}", filename);
+    }
+
+    // Daily notes may sit in dated subfolders
+    let dir = crate::get_daily_note_dir(filename);
+    let folder = dir
+        .strip_prefix(crate::get_notes_dir())
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| "daily".to_string());
+    format!("{}/{}", folder, filename)
+}
+
+/// Pinned note paths in the order they were pinned
+pub fn load() -> Vec<String> {
+    fs::read_to_string(get_pinned_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(pinned: &[String]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(pinned).map_err(|e| e.to_string())?;
+    fs::write(get_pinned_path(), json).map_err(|e| e.to_string())
+}
+
+pub fn pin(key: String) -> Result<(), String> {
+    let mut pinned = load();
+    if !pinned.contains(&key) {
+        pinned.push(key);
+        save(&pinned)?;
+    }
+    Ok(())
+}
+
+pub fn unpin(key: &str) -> Result<(), String> {
+    let mut pinned = load();
+    let before = pinned.len();
+    pinned.retain(|p| p != key);
+    if pinned.len() != before {
+        save(&pinned)?;
+    }
+    Ok(())
+}
+
+/// Keep a pin (and its position) when the note it points at is renamed
+pub fn rename(old_key: &str, new_key: String) -> Result<(), String> {
+    let mut pinned = load();
+    if let Some(entry) = pinned.iter_mut().find(|p| p.as_str() == old_key) {
+        *entry = new_key;
+        save(&pinned)?;
+    }
+    Ok(())
+}
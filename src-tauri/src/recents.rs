@@ -0,0 +1,105 @@
+use chrono::Utc;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+
+const RECENTS_FILENAME: &str = "recents.json";
+
+/// Opened notes remembered, oldest dropped first
+const MAX_RECENTS: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentEntry {
+    /// Vault-relative note path, matching `NoteFile.path`
+    pub path: String,
+    /// When the note was last opened, ISO-8601 UTC
+    pub opened_at: String,
+}
+
+/// Recents for the vault they were loaded from, most recent first
+#[derive(Default)]
+struct Recents {
+    file: Option<PathBuf>,
+    entries: Vec<RecentEntry>,
+}
+
+lazy_static! {
+    // Kept in memory so recording a read never waits on the disk
+    static ref RECENTS: Mutex<Recents> = Mutex::new(Recents::default());
+    // Serializes background writes of the file
+    static ref SAVE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+fn get_recents_path() -> PathBuf {
+    crate::get_notes_dir().join(RECENTS_FILENAME)
+}
+
+/// (Re)load the file when nothing is loaded yet or the vault has moved
+fn ensure_loaded(recents: &mut Recents) {
+    let path = get_recents_path();
+    if recents.file.as_ref() == Some(&path) {
+        return;
+    }
+
+    recents.entries = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    recents.file = Some(path);
+}
+
+/// Write the current list on a background thread. Each write takes the list
+/// as it is when the write starts, so the last one to run is never stale.
+fn save_in_background() {
+    thread::spawn(|| {
+        let Ok(_guard) = SAVE_LOCK.lock() else {
+            return;
+        };
+        let Some((path, json)) = RECENTS.lock().ok().and_then(|recents| {
+            let path = recents.file.clone()?;
+            serde_json::to_string_pretty(&recents.entries)
+                .ok()
+                .map(|json| (path, json))
+        }) else {
+            return;
+        };
+
+        if let Err(e) = fs::write(&path, json) {
+            log::warn!("Failed to save {}: {}", RECENTS_FILENAME, e);
+        }
+    });
+}
+
+/// Move a note to the front of the recents list
+pub fn record(key: String) {
+    let Ok(mut recents) = RECENTS.lock() else {
+        return;
+    };
+    ensure_loaded(&mut recents);
+
+    recents.entries.retain(|entry| entry.path != key);
+    recents.entries.insert(
+        0,
+        RecentEntry {
+            path: key,
+            opened_at: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        },
+    );
+    recents.entries.truncate(MAX_RECENTS);
+    drop(recents);
+
+    save_in_background();
+}
+
+/// Recently opened notes, most recent first
+pub fn load() -> Vec<RecentEntry> {
+    let Ok(mut recents) = RECENTS.lock() else {
+        return Vec::new();
+    };
+    ensure_loaded(&mut recents);
+    recents.entries.clone()
+}
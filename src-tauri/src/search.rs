@@ -0,0 +1,143 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::sync::Mutex;
+use std::thread;
+use tauri::{AppHandle, Emitter};
+
+/// Characters of context either side of a match in a hit's snippet
+const SNIPPET_WINDOW: usize = 40;
+
+lazy_static! {
+    // Request ids of searches still running; cancelling removes the id
+    static ref ACTIVE_SEARCHES: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SearchHitEvent {
+    request_id: String,
+    filename: String,
+    is_daily: bool,
+    title: String,
+    title_match: bool,
+    /// Text around the first match in the content, empty for title-only hits
+    snippet: String,
+    /// Byte range of the match within `snippet`, for highlighting it
+    match_start: Option<usize>,
+    match_end: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SearchDoneEvent {
+    request_id: String,
+    /// Hits emitted before the search finished or was cancelled
+    total: usize,
+    cancelled: bool,
+}
+
+fn is_active(request_id: &str) -> bool {
+    ACTIVE_SEARCHES
+        .lock()
+        .map(|active| active.contains(request_id))
+        .unwrap_or(false)
+}
+
+/// Text around `content[start..end]` on one line, and where the match
+/// sits in it. Newlines become spaces so the byte range is unchanged.
+fn snippet(content: &str, start: usize, end: usize) -> (String, usize, usize) {
+    let from = crate::offset_chars_before(content, start, SNIPPET_WINDOW);
+    let to = crate::offset_chars_after(content, end, SNIPPET_WINDOW);
+    let text = crate::context_around(content, from, to).replace('\n', " ");
+
+    let prefix = if from > 0 { "...".len() } else { 0 };
+    let offset = prefix + start - from;
+    (text, offset, offset + end - start)
+}
+
+/// Search every note on a background thread, emitting a `search-hit` event
+/// for each note whose title or content matches `pattern`, then
+/// `search-done`. The search stops between notes once cancelled.
+pub fn start(app: AppHandle, pattern: Regex, request_id: String) -> Result<(), String> {
+    let mut notes = crate::collect_notes()?;
+    notes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    ACTIVE_SEARCHES
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(request_id.clone());
+
+    thread::spawn(move || {
+        let notes_dir = crate::get_notes_dir();
+        let mut total = 0;
+        let mut cancelled = false;
+
+        for note in notes {
+            if !is_active(&request_id) {
+                cancelled = true;
+                break;
+            }
+
+            let Ok(content) = fs::read_to_string(notes_dir.join(&note.path)) else {
+                continue;
+            };
+            let title = note
+                .title
+                .clone()
+                .unwrap_or_else(|| note.name.trim_end_matches(".md").to_string());
+            let title_match = pattern.is_match(&title);
+            // Patterns like `x*` also match nothing, which isn't a hit
+            let content_match = pattern.find_iter(&content).find(|m| !m.is_empty());
+            if !title_match && content_match.is_none() {
+                continue;
+            }
+
+            let (snippet, match_start, match_end) =
+                match content_match.map(|m| snippet(&content, m.start(), m.end())) {
+                    Some((text, start, end)) => (text, Some(start), Some(end)),
+                    None => (String::new(), None, None),
+                };
+            let event = SearchHitEvent {
+                request_id: request_id.clone(),
+                filename: note.name,
+                is_daily: note.is_daily,
+                title,
+                title_match,
+                snippet,
+                match_start,
+                match_end,
+            };
+            if let Err(e) = app.emit("search-hit", event) {
+                log::warn!("Failed to emit search-hit event: {}", e);
+            }
+            total += 1;
+        }
+
+        if let Ok(mut active) = ACTIVE_SEARCHES.lock() {
+            active.remove(&request_id);
+        }
+
+        let event = SearchDoneEvent {
+            request_id,
+            total,
+            cancelled,
+        };
+        if let Err(e) = app.emit("search-done", event) {
+            log::warn!("Failed to emit search-done event: {}", e);
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop a running search; unknown or finished searches are ignored
+pub fn cancel(request_id: &str) -> Result<(), String> {
+    ACTIVE_SEARCHES
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(request_id);
+    Ok(())
+}
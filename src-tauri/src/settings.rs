@@ -0,0 +1,229 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+
+const SETTINGS_FILENAME: &str = "settings.json";
+
+/// How a note name from a wiki link becomes a filename
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum NamingStrategy {
+    /// `Meeting Notes` -> `meeting-notes.md`
+    #[default]
+    Slug,
+    /// `C++ Notes` -> `C++ Notes.md`, only dropping characters filenames can't hold
+    Verbatim,
+    /// `C++ Notes` -> `C++_Notes.md`
+    Underscore,
+}
+
+impl NamingStrategy {
+    pub const ALL: [NamingStrategy; 3] = [
+        NamingStrategy::Slug,
+        NamingStrategy::Verbatim,
+        NamingStrategy::Underscore,
+    ];
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    /// Custom vault location; the Documents default is used when unset
+    pub vault_path: Option<String>,
+    /// Calendar file read on platforms without EventKit
+    pub ics_path: Option<String>,
+    /// strftime pattern for the folders daily notes are nested in, e.g. `%Y/%m`
+    pub daily_folder_format: Option<String>,
+    /// Snapshots kept per note before the oldest are pruned
+    pub max_note_versions: Option<usize>,
+    /// Template id applied to new standalone notes created without one
+    pub default_standalone_template: Option<String>,
+    /// Template id applied to new daily notes created without one
+    pub default_daily_template: Option<String>,
+    pub naming_strategy: Option<NamingStrategy>,
+}
+
+lazy_static! {
+    // Read once so every `get_*_dir` call doesn't hit the disk
+    static ref SETTINGS: RwLock<Settings> = RwLock::new(load());
+}
+
+/// Settings live in the app config folder rather than the vault, since they
+/// decide where the vault is
+fn get_settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("com.notomattic").join(SETTINGS_FILENAME))
+}
+
+fn load() -> Settings {
+    get_settings_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(settings: &Settings) -> Result<(), String> {
+    let path = get_settings_path().ok_or("Could not find config directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Load the settings file into memory; called at startup
+pub fn init() {
+    lazy_static::initialize(&SETTINGS);
+}
+
+pub fn vault_path() -> Option<PathBuf> {
+    SETTINGS
+        .read()
+        .ok()
+        .and_then(|settings| settings.vault_path.clone())
+        .map(PathBuf::from)
+}
+
+/// Check that a folder exists and the app can create files in it
+fn validate_vault_path(path: &Path) -> Result<(), String> {
+    if !path.is_dir() {
+        return Err(format!("'{}' is not an existing folder", path.display()));
+    }
+
+    let probe = path.join(".notomattic-write-test");
+    fs::write(&probe, b"").map_err(|e| format!("'{}' is not writable: {}", path.display(), e))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// Apply a change, persisting it before it takes effect in memory
+fn update(change: impl FnOnce(&mut Settings)) -> Result<(), String> {
+    let mut settings = SETTINGS.write().map_err(|e| e.to_string())?;
+    let mut updated = settings.clone();
+    change(&mut updated);
+    save(&updated)?;
+    *settings = updated;
+    Ok(())
+}
+
+pub fn set_vault_path(path: &str) -> Result<(), String> {
+    // Stored absolute, so a relative path doesn't resolve against whatever
+    // the working directory is on the next launch
+    let path =
+        fs::canonicalize(path).map_err(|_| format!("'{}' is not an existing folder", path))?;
+    validate_vault_path(&path)?;
+
+    update(|settings| settings.vault_path = Some(path.to_string_lossy().to_string()))
+}
+
+/// Check that a folder pattern is valid strftime and can only produce
+/// folders inside the daily folder
+fn validate_daily_folder_format(format: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid daily folder format: '{}'", format);
+
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(invalid());
+    }
+
+    let sample = NaiveDate::from_ymd_opt(2024, 12, 31)
+        .unwrap()
+        .format(format)
+        .to_string();
+    let components: Vec<_> = Path::new(&sample).components().collect();
+    if components.is_empty() || !components.iter().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
+
+/// Folder pattern for daily notes; `None` keeps them at the top of the daily folder
+pub fn daily_folder_format() -> Option<String> {
+    SETTINGS
+        .read()
+        .ok()
+        .and_then(|settings| settings.daily_folder_format.clone())
+        // The file may have been edited by hand, and formatting a bad pattern panics
+        .filter(|format| validate_daily_folder_format(format).is_ok())
+}
+
+pub fn set_daily_folder_format(format: Option<&str>) -> Result<(), String> {
+    let format = format.map(str::trim).filter(|format| !format.is_empty());
+    if let Some(format) = format {
+        validate_daily_folder_format(format)?;
+    }
+
+    update(|settings| settings.daily_folder_format = format.map(str::to_string))
+}
+
+pub fn max_note_versions() -> usize {
+    SETTINGS
+        .read()
+        .ok()
+        .and_then(|settings| settings.max_note_versions)
+        .unwrap_or(crate::snapshots::DEFAULT_MAX_VERSIONS)
+}
+
+pub fn set_max_note_versions(count: usize) -> Result<(), String> {
+    if count == 0 {
+        return Err("At least one version must be kept".to_string());
+    }
+
+    update(|settings| settings.max_note_versions = Some(count))
+}
+
+pub fn naming_strategy() -> NamingStrategy {
+    SETTINGS
+        .read()
+        .ok()
+        .and_then(|settings| settings.naming_strategy)
+        .unwrap_or_default()
+}
+
+pub fn set_naming_strategy(strategy: NamingStrategy) -> Result<(), String> {
+    update(|settings| settings.naming_strategy = Some(strategy))
+}
+
+pub fn default_template(is_daily: bool) -> Option<String> {
+    SETTINGS.read().ok().and_then(|settings| {
+        if is_daily {
+            settings.default_daily_template.clone()
+        } else {
+            settings.default_standalone_template.clone()
+        }
+    })
+}
+
+pub fn set_default_template(is_daily: bool, template_id: Option<&str>) -> Result<(), String> {
+    let template_id = template_id.map(str::to_string);
+    update(|settings| {
+        if is_daily {
+            settings.default_daily_template = template_id;
+        } else {
+            settings.default_standalone_template = template_id;
+        }
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn ics_path() -> Option<PathBuf> {
+    SETTINGS
+        .read()
+        .ok()
+        .and_then(|settings| settings.ics_path.clone())
+        .map(PathBuf::from)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn set_ics_path(path: &str) -> Result<(), String> {
+    // Stored absolute, like the vault path
+    let path = fs::canonicalize(path).map_err(|_| format!("'{}' is not an existing file", path))?;
+    if !path.is_file() {
+        return Err(format!("'{}' is not an existing file", path.display()));
+    }
+
+    update(|settings| settings.ics_path = Some(path.to_string_lossy().to_string()))
+}
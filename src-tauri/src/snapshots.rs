@@ -0,0 +1,116 @@
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Millisecond precision so auto-saves in the same second don't collide
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S%3f";
+
+pub const DEFAULT_MAX_VERSIONS: usize = 20;
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteVersion {
+    /// Identifies the version when restoring it
+    pub timestamp: String,
+    pub saved_at: Option<String>,
+    pub size: u64,
+}
+
+fn get_history_dir() -> PathBuf {
+    crate::get_notes_dir().join(".history")
+}
+
+fn note_history_dir(filename: &str, is_daily: bool) -> Result<PathBuf, String> {
+    if Path::new(filename).file_name() != Some(std::ffi::OsStr::new(filename)) {
+        return Err(format!("Invalid note name: '{}'", filename));
+    }
+    let folder = if is_daily { "daily" } else { "notes" };
+    Ok(get_history_dir().join(folder).join(filename))
+}
+
+/// Version timestamps for a note, oldest first (the format sorts as text)
+fn version_timestamps(dir: &Path) -> Vec<String> {
+    let mut timestamps: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    name.strip_suffix(".md").map(str::to_string)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    timestamps.sort();
+    timestamps
+}
+
+/// Store a copy of `content` as the newest version of a note, then delete
+/// all but the `max_versions` most recent
+pub fn snapshot(
+    filename: &str,
+    is_daily: bool,
+    content: &str,
+    max_versions: usize,
+) -> Result<(), String> {
+    let dir = note_history_dir(filename, is_daily)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let timestamps = version_timestamps(&dir);
+    // Identical consecutive saves would only push older versions out
+    if let Some(latest) = timestamps.last() {
+        if fs::read_to_string(dir.join(format!("{}.md", latest)))
+            .ok()
+            .as_deref()
+            == Some(content)
+        {
+            return Ok(());
+        }
+    }
+
+    let timestamp = Local::now().format(TIMESTAMP_FORMAT).to_string();
+    fs::write(dir.join(format!("{}.md", timestamp)), content).map_err(|e| e.to_string())?;
+
+    let timestamps = version_timestamps(&dir);
+    let excess = timestamps.len().saturating_sub(max_versions.max(1));
+    for old in &timestamps[..excess] {
+        let _ = fs::remove_file(dir.join(format!("{}.md", old)));
+    }
+
+    Ok(())
+}
+
+/// Saved versions of a note, newest first
+pub fn list(filename: &str, is_daily: bool) -> Result<Vec<NoteVersion>, String> {
+    let dir = note_history_dir(filename, is_daily)?;
+
+    let versions = version_timestamps(&dir)
+        .into_iter()
+        .rev()
+        .map(|timestamp| {
+            let size = fs::metadata(dir.join(format!("{}.md", timestamp)))
+                .map(|m| m.len())
+                .unwrap_or(0);
+            let saved_at = NaiveDateTime::parse_from_str(&timestamp, TIMESTAMP_FORMAT)
+                .ok()
+                .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string());
+            NoteVersion {
+                timestamp,
+                saved_at,
+                size,
+            }
+        })
+        .collect();
+
+    Ok(versions)
+}
+
+pub fn read(filename: &str, is_daily: bool, timestamp: &str) -> Result<String, String> {
+    let dir = note_history_dir(filename, is_daily)?;
+    if !version_timestamps(&dir).iter().any(|t| t == timestamp) {
+        return Err(format!("No version '{}' of '{}'", timestamp, filename));
+    }
+
+    fs::read_to_string(dir.join(format!("{}.md", timestamp))).map_err(|e| e.to_string())
+}
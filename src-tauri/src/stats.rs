@@ -0,0 +1,207 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Average adult reading speed used for reading time estimates
+const WORDS_PER_MINUTE: usize = 200;
+
+lazy_static! {
+    static ref IMAGE_REGEX: Regex = Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap();
+    static ref MD_LINK_REGEX: Regex = Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap();
+    static ref HTML_TAG_REGEX: Regex = Regex::new(r"<[^>]+>").unwrap();
+    static ref BLOCK_PREFIX_REGEX: Regex =
+        Regex::new(r"^\s*(?:#{1,6}\s+|>\s*|[-*+]\s+(?:\[[ xX]\]\s+)?|\d+[.)]\s+)").unwrap();
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteStats {
+    pub word_count: usize,
+    pub char_count: usize,
+    pub line_count: usize,
+    pub reading_time_minutes: usize,
+}
+
+/// Parts of a note left out of word and character counts. By default only
+/// prose is counted: frontmatter and code blocks are skipped, while wiki
+/// links count as the text they display.
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WordCountOptions {
+    pub exclude_frontmatter: bool,
+    /// Fenced code blocks, contents included
+    pub exclude_code: bool,
+    /// Wiki links, display text included
+    pub exclude_wiki_links: bool,
+}
+
+impl Default for WordCountOptions {
+    fn default() -> Self {
+        WordCountOptions {
+            exclude_frontmatter: true,
+            exclude_code: true,
+            exclude_wiki_links: false,
+        }
+    }
+}
+
+/// Reduce markdown to its prose: drop frontmatter, block markers (headings,
+/// quotes, list bullets), link and image syntax, emphasis and inline HTML
+pub fn strip_markdown(content: &str) -> String {
+    let options = WordCountOptions {
+        exclude_code: false,
+        ..WordCountOptions::default()
+    };
+    strip_markdown_with(content, &options)
+}
+
+/// `strip_markdown`, choosing which parts of the note to leave out
+pub fn strip_markdown_with(content: &str, options: &WordCountOptions) -> String {
+    let text = if options.exclude_frontmatter {
+        crate::frontmatter::parse_frontmatter(content).1
+    } else {
+        content.to_string()
+    };
+
+    let mut prose = Vec::new();
+    let mut in_code_fence = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_fence = !in_code_fence;
+            continue;
+        }
+        if in_code_fence && options.exclude_code {
+            continue;
+        }
+
+        let line = BLOCK_PREFIX_REGEX.replace(line, "");
+        let line = if options.exclude_wiki_links {
+            crate::WIKI_LINK_REGEX.replace_all(&line, " ")
+        } else {
+            crate::WIKI_LINK_REGEX.replace_all(&line, "$1")
+        };
+        let line = IMAGE_REGEX.replace_all(&line, "$1");
+        let line = MD_LINK_REGEX.replace_all(&line, "$1");
+        let line = HTML_TAG_REGEX.replace_all(&line, " ");
+        let line: String = line
+            .chars()
+            .filter(|c| !matches!(c, '*' | '_' | '~' | '`'))
+            .collect();
+
+        prose.push(line);
+    }
+
+    prose.join("\n")
+}
+
+/// Count words as whitespace-separated tokens containing a letter or digit
+pub fn count_words(prose: &str) -> usize {
+    prose
+        .split_whitespace()
+        .filter(|token| token.chars().any(|c| c.is_alphanumeric()))
+        .count()
+}
+
+pub fn compute(content: &str, options: &WordCountOptions) -> NoteStats {
+    let prose = strip_markdown_with(content, options);
+    let word_count = count_words(&prose);
+
+    NoteStats {
+        word_count,
+        char_count: prose.chars().filter(|c| *c != '\n').count(),
+        line_count: content.lines().count(),
+        reading_time_minutes: word_count.div_ceil(WORDS_PER_MINUTE),
+    }
+}
+
+/// `NoteStats` plus structure counts, for live stats on unsaved editor content
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentStats {
+    pub word_count: usize,
+    pub char_count: usize,
+    pub line_count: usize,
+    pub reading_time_minutes: usize,
+    /// ATX headings outside fenced code blocks
+    pub heading_count: usize,
+    /// Wiki links and markdown links; images aren't counted
+    pub link_count: usize,
+}
+
+pub fn analyze(content: &str, options: &WordCountOptions) -> ContentStats {
+    let stats = compute(content, options);
+
+    let mut heading_count = 0;
+    let mut in_code_fence = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_fence = !in_code_fence;
+        } else if !in_code_fence && crate::export::parse_heading(line).is_some() {
+            heading_count += 1;
+        }
+    }
+
+    let markdown_links = MD_LINK_REGEX
+        .find_iter(content)
+        .filter(|m| !content[..m.start()].ends_with('!'))
+        .count();
+
+    ContentStats {
+        word_count: stats.word_count,
+        char_count: stats.char_count,
+        line_count: stats.line_count,
+        reading_time_minutes: stats.reading_time_minutes,
+        heading_count,
+        link_count: crate::parse_wiki_links(content).len() + markdown_links,
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultStats {
+    pub note_count: usize,
+    pub daily_count: usize,
+    pub standalone_count: usize,
+    pub word_count: usize,
+    /// Distinct tags across the vault
+    pub tag_count: usize,
+    pub average_word_count: usize,
+    /// Earliest and latest daily note dates, `YYYY-MM-DD`
+    pub first_daily: Option<String>,
+    pub last_daily: Option<String>,
+}
+
+impl VaultStats {
+    /// Count one note; `daily_date` is set for daily notes named by date
+    pub fn add_note(
+        &mut self,
+        is_daily: bool,
+        daily_date: Option<&str>,
+        content: &str,
+        options: &WordCountOptions,
+    ) {
+        self.note_count += 1;
+        if is_daily {
+            self.daily_count += 1;
+        } else {
+            self.standalone_count += 1;
+        }
+        self.word_count += count_words(&strip_markdown_with(content, options));
+
+        if let Some(date) = daily_date {
+            // ISO dates order the same as text
+            let first = self.first_daily.get_or_insert_with(|| date.to_string());
+            if date < first.as_str() {
+                *first = date.to_string();
+            }
+            let last = self.last_daily.get_or_insert_with(|| date.to_string());
+            if date > last.as_str() {
+                *last = date.to_string();
+            }
+        }
+
+        self.average_word_count = self.word_count / self.note_count;
+    }
+}
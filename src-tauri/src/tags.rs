@@ -0,0 +1,51 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeSet;
+
+lazy_static! {
+    // A `#tag` preceded by start-of-text or a non-word character, so URL
+    // fragments and `[[Note#Section]]` links aren't picked up
+    static ref TAG_REGEX: Regex = Regex::new(r"(?:^|[^\w&#/\[])#([A-Za-z][\w/-]*)").unwrap();
+}
+
+/// Extract the distinct, lowercased tags of a note: frontmatter `tags` plus
+/// inline `#hashtags` outside fenced code blocks and heading markers
+pub fn extract_tags(content: &str) -> BTreeSet<String> {
+    let (frontmatter, body) = crate::frontmatter::parse_frontmatter(content);
+
+    let mut tags: BTreeSet<String> = frontmatter
+        .and_then(|fm| fm.tags)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| t.trim_start_matches('#').to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut in_code_fence = false;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_fence = !in_code_fence;
+            continue;
+        }
+        if in_code_fence {
+            continue;
+        }
+
+        // A `#` opening the line is a heading marker, not a tag
+        let scan_from = if trimmed.starts_with('#') {
+            &trimmed[trimmed.find(|c: char| c != '#').unwrap_or(trimmed.len())..]
+        } else {
+            trimmed
+        };
+
+        for cap in TAG_REGEX.captures_iter(scan_from) {
+            if let Some(tag) = cap.get(1) {
+                tags.insert(tag.as_str().trim_end_matches(['/', '-']).to_lowercase());
+            }
+        }
+    }
+
+    tags
+}
@@ -0,0 +1,149 @@
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    /// Path relative to the trash folder, e.g. `notes/my-note.20240614-153012.md`
+    pub trash_filename: String,
+    pub original_filename: String,
+    pub original_path: String,
+    pub is_daily: bool,
+    pub deleted_at: Option<String>,
+}
+
+pub fn get_trash_dir() -> PathBuf {
+    crate::get_notes_dir().join(".trash")
+}
+
+fn subfolder(is_daily: bool) -> &'static str {
+    if is_daily {
+        "daily"
+    } else {
+        "notes"
+    }
+}
+
+/// Split `name.<timestamp>.md` into the original filename and the timestamp
+fn parse_trash_name(trash_name: &str) -> Option<(String, &str)> {
+    let stem = trash_name.strip_suffix(".md")?;
+    let (original_stem, suffix) = stem.rsplit_once('.')?;
+    // Collision counters ("-1", "-2") follow the fixed-width timestamp
+    let timestamp = suffix.get(..15)?;
+    Some((format!("{}.md", original_stem), timestamp))
+}
+
+/// Move a note into the trash, returning its path relative to the trash folder
+pub fn move_to_trash(path: &Path, is_daily: bool) -> Result<String, String> {
+    let folder = subfolder(is_daily);
+    let trash_dir = get_trash_dir().join(folder);
+    fs::create_dir_all(&trash_dir).map_err(|e| e.to_string())?;
+
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("Invalid note filename")?;
+    let timestamp = Local::now().format(TIMESTAMP_FORMAT).to_string();
+
+    let mut trash_name = format!("{}.{}.md", stem, timestamp);
+    let mut counter = 1;
+    while trash_dir.join(&trash_name).exists() {
+        trash_name = format!("{}.{}-{}.md", stem, timestamp, counter);
+        counter += 1;
+    }
+
+    fs::rename(path, trash_dir.join(&trash_name)).map_err(|e| e.to_string())?;
+
+    Ok(format!("{}/{}", folder, trash_name))
+}
+
+/// List everything currently in the trash, newest first
+pub fn list() -> Result<Vec<TrashEntry>, String> {
+    let mut entries = Vec::new();
+
+    for is_daily in [true, false] {
+        let folder = subfolder(is_daily);
+        let dir = get_trash_dir().join(folder);
+        if !dir.exists() {
+            continue;
+        }
+
+        let dir_entries = fs::read_dir(&dir).map_err(|e| e.to_string())?;
+        for entry in dir_entries.flatten() {
+            let trash_name = entry.file_name().to_string_lossy().to_string();
+            let Some((original_filename, timestamp)) = parse_trash_name(&trash_name) else {
+                continue;
+            };
+
+            let deleted_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT)
+                .ok()
+                .map(|dt| dt.format("%Y-%m-%dT%H:%M:%S").to_string());
+
+            entries.push(TrashEntry {
+                trash_filename: format!("{}/{}", folder, trash_name),
+                original_path: format!("{}/{}", folder, original_filename),
+                original_filename,
+                is_daily,
+                deleted_at,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    Ok(entries)
+}
+
+/// Move a trashed note back to where it came from, returning its filename
+pub fn restore(trash_filename: &str) -> Result<String, String> {
+    let (folder, trash_name) = trash_filename
+        .split_once('/')
+        .ok_or("Invalid trash filename")?;
+
+    let is_daily = match folder {
+        "daily" => true,
+        "notes" => false,
+        _ => return Err("Invalid trash filename".to_string()),
+    };
+
+    if trash_name.contains('/') || trash_name.contains('\\') || trash_name.contains("..") {
+        return Err("Invalid trash filename".to_string());
+    }
+
+    let trash_path = get_trash_dir().join(folder).join(trash_name);
+    if !trash_path.exists() {
+        return Err(format!("'{}' is not in the trash", trash_filename));
+    }
+
+    let (original_filename, _) = parse_trash_name(trash_name).ok_or("Invalid trash filename")?;
+    let dir = if is_daily {
+        crate::get_daily_note_dir(&original_filename)
+    } else {
+        crate::get_standalone_dir()
+    };
+    let restored_path = dir.join(&original_filename);
+
+    if restored_path.exists() {
+        return Err(format!(
+            "A note named '{}' already exists",
+            original_filename
+        ));
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    fs::rename(&trash_path, &restored_path).map_err(|e| e.to_string())?;
+
+    Ok(original_filename)
+}
+
+/// Permanently delete everything in the trash
+pub fn empty() -> Result<(), String> {
+    let trash_dir = get_trash_dir();
+    if trash_dir.exists() {
+        fs::remove_dir_all(&trash_dir).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,293 @@
+use crate::pins;
+use chrono::Local;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Maximum number of destructive operations kept in the log
+const MAX_ENTRIES: usize = 20;
+
+/// Entries older than this (in seconds) are dropped, so undo survives a
+/// restart but not indefinitely
+const UNDO_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+const UNDO_LOG_FILENAME: &str = ".undo.json";
+
+lazy_static! {
+    // Serializes read-modify-write cycles on the log file
+    static ref UNDO_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// A note whose links were rewritten along with a rename
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkedNote {
+    pub filename: String,
+    pub is_daily: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum UndoAction {
+    #[serde(rename_all = "camelCase")]
+    Delete { trash_filename: String },
+    /// Several notes deleted together, undone together
+    #[serde(rename_all = "camelCase")]
+    DeleteMany { trash_filenames: Vec<String> },
+    #[serde(rename_all = "camelCase")]
+    Rename {
+        old_filename: String,
+        new_filename: String,
+        is_daily: bool,
+        /// Notes whose links were pointed at the new name, pointed back on undo
+        #[serde(default)]
+        relinked: Vec<RelinkedNote>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Move {
+        filename: String,
+        from_daily: bool,
+        to_daily: bool,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct UndoEntry {
+    timestamp: i64,
+    action: UndoAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoResult {
+    pub description: String,
+    pub restored: Vec<String>,
+}
+
+fn get_undo_log_path() -> PathBuf {
+    crate::get_notes_dir().join(UNDO_LOG_FILENAME)
+}
+
+fn note_path(filename: &str, is_daily: bool) -> PathBuf {
+    let dir = if is_daily {
+        crate::get_daily_note_dir(filename)
+    } else {
+        crate::get_standalone_dir()
+    };
+    dir.join(filename)
+}
+
+/// Recreate the folder a note is being put back into; a daily note's dated
+/// folder may have been removed since
+fn ensure_parent(path: &Path) -> Result<(), String> {
+    match path.parent() {
+        Some(parent) => fs::create_dir_all(parent).map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
+}
+
+fn load_entries(path: &Path) -> Vec<UndoEntry> {
+    let entries: Vec<UndoEntry> = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let cutoff = Local::now().timestamp() - UNDO_WINDOW_SECS;
+    entries
+        .into_iter()
+        .filter(|e| e.timestamp >= cutoff)
+        .collect()
+}
+
+fn save_entries(path: &Path, entries: &[UndoEntry]) -> Result<(), String> {
+    let json = serde_json::to_string(entries).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Push a destructive operation onto the persisted undo log
+pub fn record(action: UndoAction) -> Result<(), String> {
+    let _guard = UNDO_LOCK.lock().map_err(|e| e.to_string())?;
+
+    let path = get_undo_log_path();
+    let mut entries = load_entries(&path);
+    entries.push(UndoEntry {
+        timestamp: Local::now().timestamp(),
+        action,
+    });
+
+    if entries.len() > MAX_ENTRIES {
+        let excess = entries.len() - MAX_ENTRIES;
+        entries.drain(..excess);
+    }
+
+    save_entries(&path, &entries)
+}
+
+fn reverse(action: &UndoAction) -> Result<UndoResult, String> {
+    match action {
+        UndoAction::Delete { trash_filename } => {
+            let filename = crate::trash::restore(trash_filename)?;
+            Ok(UndoResult {
+                description: format!("Restored deleted note '{}'", filename),
+                restored: vec![filename],
+            })
+        }
+        UndoAction::DeleteMany { trash_filenames } => {
+            let mut restored = Vec::new();
+            for trash_filename in trash_filenames {
+                // A note that can't come back (its name was reused since)
+                // doesn't block the rest
+                match crate::trash::restore(trash_filename) {
+                    Ok(filename) => restored.push(filename),
+                    Err(e) => log::warn!("Skipping restore of '{}': {}", trash_filename, e),
+                }
+            }
+            Ok(UndoResult {
+                description: format!("Restored {} deleted notes", restored.len()),
+                restored,
+            })
+        }
+        UndoAction::Rename {
+            old_filename,
+            new_filename,
+            is_daily,
+            relinked,
+        } => {
+            let old_path = note_path(old_filename, *is_daily);
+            let new_path = note_path(new_filename, *is_daily);
+
+            if !new_path.exists() {
+                return Err(format!("Note '{}' no longer exists", new_filename));
+            }
+            if old_path.exists() {
+                return Err(format!("A note named '{}' already exists", old_filename));
+            }
+
+            // The pin key is the note's path, so it has to be read before the move
+            let new_key = pins::note_key(new_filename, *is_daily);
+            ensure_parent(&old_path)?;
+            fs::rename(&new_path, &old_path).map_err(|e| e.to_string())?;
+            if let Err(e) = pins::rename(&new_key, pins::note_key(old_filename, *is_daily)) {
+                log::warn!("Failed to update pinned notes: {}", e);
+            }
+
+            // Links are rewritten back rather than restored from a copy, so
+            // edits made since the rename are kept
+            let old_target = old_filename.trim_end_matches(".md");
+            for note in relinked {
+                let path = note_path(&note.filename, note.is_daily);
+                let Ok(content) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                if let Some(rewritten) = crate::rewrite_links_to(&content, new_filename, old_target)
+                {
+                    if let Err(e) = crate::write_atomic(&path, &rewritten) {
+                        log::warn!("Failed to restore links in '{}': {}", note.filename, e);
+                    }
+                }
+            }
+
+            Ok(UndoResult {
+                description: format!("Renamed '{}' back to '{}'", new_filename, old_filename),
+                restored: vec![old_filename.clone()],
+            })
+        }
+        UndoAction::Move {
+            filename,
+            from_daily,
+            to_daily,
+        } => {
+            let original_path = note_path(filename, *from_daily);
+            let moved_path = note_path(filename, *to_daily);
+
+            if !moved_path.exists() {
+                return Err(format!("Note '{}' no longer exists", filename));
+            }
+            if original_path.exists() {
+                return Err(format!("A note named '{}' already exists", filename));
+            }
+
+            let moved_key = pins::note_key(filename, *to_daily);
+            ensure_parent(&original_path)?;
+            fs::rename(&moved_path, &original_path).map_err(|e| e.to_string())?;
+            if let Err(e) = pins::rename(&moved_key, pins::note_key(filename, *from_daily)) {
+                log::warn!("Failed to update pinned notes: {}", e);
+            }
+            let folder = if *from_daily { "daily" } else { "notes" };
+            Ok(UndoResult {
+                description: format!("Moved '{}' back to {}", filename, folder),
+                restored: vec![filename.clone()],
+            })
+        }
+    }
+}
+
+/// Reverse the most recent destructive operation still inside the undo window
+pub fn undo_last() -> Result<UndoResult, String> {
+    let _guard = UNDO_LOCK.lock().map_err(|e| e.to_string())?;
+    undo_last_in(&get_undo_log_path(), reverse)
+}
+
+/// Pop the newest entry of the log at `log_path` and reverse it. The entry
+/// is dropped even when reversing fails (the note was deleted or its old
+/// name reused since), so one stuck entry doesn't block every older one.
+fn undo_last_in(
+    log_path: &Path,
+    reverse: impl FnOnce(&UndoAction) -> Result<UndoResult, String>,
+) -> Result<UndoResult, String> {
+    let mut entries = load_entries(log_path);
+    let entry = entries.pop().ok_or("Nothing to undo")?;
+
+    let result = reverse(&entry.action);
+    save_entries(log_path, &entries)?;
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename_entry(old_filename: &str, new_filename: &str) -> UndoEntry {
+        UndoEntry {
+            timestamp: Local::now().timestamp(),
+            action: UndoAction::Rename {
+                old_filename: old_filename.to_string(),
+                new_filename: new_filename.to_string(),
+                is_daily: false,
+                relinked: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn an_entry_that_cannot_be_reversed_does_not_block_older_ones() {
+        let dir = std::env::temp_dir().join(format!("notomattic-undo-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let log_path = dir.join(UNDO_LOG_FILENAME);
+        let entries = [rename_entry("a.md", "b.md"), rename_entry("c.md", "d.md")];
+        save_entries(&log_path, &entries).unwrap();
+
+        let stuck = undo_last_in(&log_path, |_| {
+            Err("Note 'd.md' no longer exists".to_string())
+        });
+        assert!(stuck.is_err());
+        assert_eq!(load_entries(&log_path).len(), 1);
+
+        let undone = undo_last_in(&log_path, |action| match action {
+            UndoAction::Rename { old_filename, .. } => Ok(UndoResult {
+                description: String::new(),
+                restored: vec![old_filename.clone()],
+            }),
+            _ => Err("unexpected entry".to_string()),
+        })
+        .unwrap();
+        assert_eq!(undone.restored, vec!["a.md".to_string()]);
+        assert!(load_entries(&log_path).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
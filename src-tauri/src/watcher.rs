@@ -0,0 +1,150 @@
+use lazy_static::lazy_static;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::link_index::LinkIndexState;
+
+/// Quiet period before queued changes are emitted, so one save that
+/// produces several filesystem events is reported once
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+lazy_static! {
+    // Dropping the active watcher closes its channel, which ends its thread
+    static ref ACTIVE_WATCHER: Mutex<Option<RecommendedWatcher>> = Mutex::new(None);
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct NoteChangedEvent {
+    /// Path relative to the vault root, e.g. `daily/2024-06-14.md`
+    path: String,
+    kind: ChangeKind,
+    is_daily: bool,
+}
+
+fn classify(kind: &EventKind, path: &Path) -> Option<ChangeKind> {
+    match kind {
+        EventKind::Create(_) => Some(ChangeKind::Created),
+        EventKind::Remove(_) => Some(ChangeKind::Deleted),
+        // Renames report both ends; whichever side still exists was created
+        EventKind::Modify(notify::event::ModifyKind::Name(_)) => Some(if path.exists() {
+            ChangeKind::Created
+        } else {
+            ChangeKind::Deleted
+        }),
+        EventKind::Modify(_) => Some(ChangeKind::Modified),
+        _ => None,
+    }
+}
+
+/// Apply a debounced change to the cached link index and notify the frontend
+fn flush(app: &AppHandle, notes_dir: &Path, pending: &mut HashMap<PathBuf, ChangeKind>) {
+    let ignore_rules = crate::load_ignore_rules();
+
+    for (path, kind) in pending.drain() {
+        let Ok(relative) = path.strip_prefix(notes_dir) else {
+            continue;
+        };
+
+        // Only notes in daily/ and notes/ matter; hidden folders like .trash are skipped
+        let mut components = relative.components();
+        let is_daily = match components.next() {
+            Some(Component::Normal(folder)) if folder == "daily" => true,
+            Some(Component::Normal(folder)) if folder == "notes" => false,
+            _ => continue,
+        };
+
+        if path.extension().and_then(|s| s.to_str()) != Some("md")
+            || crate::is_ignored(&ignore_rules, &path)
+        {
+            continue;
+        }
+
+        // An edit made elsewhere may have removed the note's aliases, which
+        // `update` can't tell from the new content
+        crate::aliases::invalidate();
+        if let Ok(mut cache) = app.state::<LinkIndexState>().0.lock() {
+            match std::fs::read_to_string(&path) {
+                Ok(content) => cache.update(&path, is_daily, &content),
+                Err(_) => {
+                    if let Some(filename) = path.file_name().and_then(|s| s.to_str()) {
+                        cache.remove(filename, is_daily);
+                    }
+                }
+            }
+        }
+
+        let event = NoteChangedEvent {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            kind,
+            is_daily,
+        };
+        if let Err(e) = app.emit("note-changed", event) {
+            log::warn!("Failed to emit note-changed event: {}", e);
+        }
+    }
+}
+
+/// Watch the vault in a background thread and emit `note-changed` events
+/// for notes created, modified, or deleted outside the app. Calling this
+/// again (e.g. after the vault moves) replaces the previous watcher.
+pub fn start(app: AppHandle) -> Result<(), String> {
+    let notes_dir = crate::get_notes_dir();
+    std::fs::create_dir_all(&notes_dir).map_err(|e| e.to_string())?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|e| e.to_string())?;
+    watcher
+        .watch(&notes_dir, RecursiveMode::Recursive)
+        .map_err(|e| e.to_string())?;
+
+    *ACTIVE_WATCHER.lock().map_err(|e| e.to_string())? = Some(watcher);
+
+    thread::spawn(move || {
+        let mut pending: HashMap<PathBuf, ChangeKind> = HashMap::new();
+
+        loop {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(Ok(event)) => {
+                    for path in event.paths {
+                        let Some(kind) = classify(&event.kind, &path) else {
+                            continue;
+                        };
+                        // A create followed by writes is still a create
+                        let merged = match pending.get(&path) {
+                            Some(ChangeKind::Created) if kind == ChangeKind::Modified => {
+                                ChangeKind::Created
+                            }
+                            _ => kind,
+                        };
+                        pending.insert(path, merged);
+                    }
+                }
+                Ok(Err(e)) => log::warn!("File watcher error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {
+                    if !pending.is_empty() {
+                        flush(&app, &notes_dir, &mut pending);
+                    }
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(())
+}
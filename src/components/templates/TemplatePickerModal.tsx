@@ -23,10 +23,12 @@ export function TemplatePickerModal({
   const { loadError, loadTemplates } = useTemplates();
 
   const filteredTemplates = useMemo(() => {
-    if (!searchQuery.trim()) return templates;
+    // Snippets are inserted into notes, not used to start them
+    const noteTemplates = templates.filter((t) => !t.isSnippet);
+    if (!searchQuery.trim()) return noteTemplates;
 
     const query = searchQuery.toLowerCase();
-    return templates.filter(
+    return noteTemplates.filter(
       (t) =>
         t.name.toLowerCase().includes(query) ||
         t.description.toLowerCase().includes(query)
@@ -26,6 +26,15 @@ export async function isCalendarAuthorized(): Promise<boolean> {
   return await invoke('is_calendar_authorized');
 }
 
+/**
+ * Checks the calendar permission status again instead of using the cached
+ * one, e.g. after the user changed it in System Settings.
+ * @returns The current permission status
+ */
+export async function refreshCalendarPermission(): Promise<CalendarPermission> {
+  return await invoke('refresh_calendar_permission');
+}
+
 /**
  * Fetches calendar events for a date range.
  * @param startDate - Start date in YYYY-MM-DD format
@@ -45,6 +54,17 @@ export async function fetchCalendarEvents(
   });
 }
 
+/**
+ * Fetches one day's events, from local midnight to midnight, sorted by start
+ * time with all-day events first.
+ * @param date - Date in YYYY-MM-DD format
+ * @param calendarId - Optional calendar ID to filter by specific calendar
+ * @returns Array of calendar events
+ */
+export async function fetchEventsForDay(date: string, calendarId?: string): Promise<CalendarEvent[]> {
+  return await invoke('fetch_events_for_day', { date, calendarId: calendarId || null });
+}
+
 /**
  * Lists all calendars from the user's Calendar.app.
  * @returns Array of calendar info objects with IDs and names
@@ -52,3 +72,31 @@ export async function fetchCalendarEvents(
 export async function listCalendars(): Promise<CalendarInfo[]> {
   return await invoke('list_calendars');
 }
+
+/**
+ * Adds a `## Schedule` section listing the day's calendar events to that
+ * day's daily note, creating the note if needed. Does nothing if the note
+ * already has a schedule section.
+ * @param date - Date in YYYY-MM-DD format
+ */
+export async function insertEventsIntoDaily(date: string): Promise<void> {
+  await invoke('insert_events_into_daily', { date });
+}
+
+/**
+ * Gets the `.ics` file used for calendar events on platforms without
+ * Apple Calendar (Linux and Windows).
+ * @returns The configured file path, or null if none is set (always null on macOS)
+ */
+export async function getIcsPath(): Promise<string | null> {
+  return await invoke('get_ics_path');
+}
+
+/**
+ * Sets the `.ics` file to read calendar events from on Linux and Windows.
+ * @param path - Absolute path to an existing `.ics` file
+ * @throws {Error} On macOS, which reads Apple Calendar instead
+ */
+export async function setIcsPath(path: string): Promise<void> {
+  await invoke('set_ics_path', { path });
+}
@@ -1,5 +1,37 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Note, NoteFile } from '@/types';
+import type {
+  BrokenLink,
+  ClearScope,
+  Commit,
+  ContentStats,
+  DayCount,
+  DeleteImpact,
+  Direction,
+  DeleteReport,
+  DuplicateGroup,
+  Heading,
+  ImportReport,
+  LinkGraph,
+  NamingStrategy,
+  Note,
+  NoteBundle,
+  NoteExistence,
+  NoteFile,
+  NotePage,
+  NoteRef,
+  NoteSortKey,
+  NoteStats,
+  NoteVersion,
+  RelinkReport,
+  ReplaceReport,
+  TagCount,
+  TrashEntry,
+  UndoResult,
+  VaultStats,
+  VersionedNote,
+  WikiLink,
+  WordCountOptions,
+} from '@/types';
 import { format, parse, isValid } from 'date-fns';
 import TurndownService from 'turndown';
 import MarkdownIt from 'markdown-it';
@@ -138,7 +170,8 @@ export function markdownToHtml(markdown: string): string {
     /\[\[([^\]|]+)(?:\|([^\]]+))?\]\]/g,
     (_match, text, target) => {
       const displayText = text.trim();
-      const targetNote = (target || text).trim();
+      // Drop any "#Section" suffix - the note itself is the link target
+      const targetNote = (target || text).split('#')[0].trim();
       // Convert to filename format
       const filename = noteNameToFilename(targetNote);
 
@@ -180,36 +213,250 @@ export async function ensureDirectories(): Promise<void> {
   await invoke('ensure_directories');
 }
 
+/**
+ * Gets the absolute path of the vault folder notes are stored in.
+ * @returns The configured vault path, or the Documents default
+ */
+export async function getVaultPath(): Promise<string> {
+  return await invoke('get_vault_path');
+}
+
+/**
+ * Points the app at a different vault folder. The folder must exist and be
+ * writable; the choice is persisted across restarts.
+ * @param path - Absolute path of the new vault folder
+ */
+export async function setVaultPath(path: string): Promise<void> {
+  await invoke('set_vault_path', { path });
+}
+
+/**
+ * Gets the folder pattern daily notes are nested in, if one is set.
+ * @returns A strftime pattern such as "%Y/%m", or null for a flat daily folder
+ */
+export async function getDailyFolderFormat(): Promise<string | null> {
+  return await invoke('get_daily_folder_format');
+}
+
+/**
+ * Sets the folder pattern new daily notes are created in, e.g. "%Y/%m" for
+ * daily/2025/01/2025-01-31.md. Existing notes are not moved.
+ * @param format - strftime pattern, or null to keep daily notes unnested
+ */
+export async function setDailyFolderFormat(format: string | null): Promise<void> {
+  await invoke('set_daily_folder_format', { format: format || null });
+}
+
+/**
+ * Gets the gitignore-style patterns for notes the app skips.
+ * @returns Patterns from .notomatticignore, without comments
+ */
+export async function getIgnorePatterns(): Promise<string[]> {
+  return await invoke('get_ignore_patterns');
+}
+
+/**
+ * Replaces the ignore patterns, matched against paths relative to the vault
+ * root (e.g. "_drafts/" or "notes/scratch-*.md"). Comments in the file are kept.
+ * @param patterns - Gitignore-style patterns
+ * @throws {Error} If a pattern is invalid
+ */
+export async function setIgnorePatterns(patterns: string[]): Promise<void> {
+  await invoke('set_ignore_patterns', { patterns });
+}
+
 /**
  * Lists all notes (both daily and standalone) from the file system.
  * @returns Array of note file metadata
  */
 export async function listNotes(): Promise<NoteFile[]> {
-  return await invoke('list_notes');
+  const page = await invoke<NotePage>('list_notes');
+  return page.notes;
+}
+
+/**
+ * Finds the nearest existing daily note before or after a date, skipping
+ * days without a note.
+ * @param date - Date to start from (YYYY-MM-DD)
+ * @param direction - 'Previous' or 'Next'
+ * @returns The daily note, or null when there is none in that direction
+ */
+export async function adjacentDailyNote(date: string, direction: Direction): Promise<NoteFile | null> {
+  return await invoke('adjacent_daily_note', { date, direction });
+}
+
+/**
+ * Lists daily notes written on today's month and day in earlier years.
+ * @returns Daily notes, newest first
+ */
+export async function notesOnThisDay(): Promise<NoteFile[]> {
+  return await invoke('notes_on_this_day');
+}
+
+/**
+ * Gets the word count of each daily note in a year, e.g. for a heatmap.
+ * @param year - The calendar year
+ * @returns One entry per day with a daily note, in date order
+ */
+export async function dailyNoteCounts(year: number): Promise<DayCount[]> {
+  return await invoke('daily_note_counts', { year });
+}
+
+/**
+ * Lists notes last modified within a date range, most recent first.
+ * @param start - First day of the range (YYYY-MM-DD, inclusive)
+ * @param end - Last day of the range (YYYY-MM-DD, inclusive)
+ * @returns Notes modified in the range
+ * @throws {Error} If a date is invalid or start is after end
+ */
+export async function notesModifiedBetween(start: string, end: string): Promise<NoteFile[]> {
+  return await invoke('notes_modified_between', { start, end });
+}
+
+/**
+ * Lists notes sorted and paginated on the backend.
+ * @param options.sortBy - Sort by name, modification time, or creation time (directory order if omitted)
+ * @param options.descending - Reverse the sort order
+ * @param options.limit - Maximum number of notes to return
+ * @param options.offset - Number of notes to skip
+ * @param options.withPreview - Include a short plain-text preview of each note
+ * @param options.includeExtensions - Also list files with these extensions, e.g. ['txt', 'canvas']
+ * @returns The requested page of notes and the total note count
+ */
+export async function listNotesPage(options: {
+  sortBy?: NoteSortKey;
+  descending?: boolean;
+  limit?: number;
+  offset?: number;
+  withPreview?: boolean;
+  includeExtensions?: string[];
+} = {}): Promise<NotePage> {
+  return await invoke('list_notes', {
+    sortBy: options.sortBy || null,
+    descending: options.descending || null,
+    limit: options.limit ?? null,
+    offset: options.offset ?? null,
+    withPreview: options.withPreview || null,
+    includeExtensions: options.includeExtensions || null,
+  });
 }
 
 /**
- * Reads the content of a specific note file.
+ * Reads the content of a specific note file. Other text files (e.g. .txt)
+ * can be read too.
  * @param filename - The note filename (e.g., "2025-01-01.md")
  * @param isDaily - Whether this is a daily note
  * @returns The raw Markdown content
+ * @throws {Error} If the file is binary rather than text
  */
 export async function readNote(filename: string, isDaily: boolean): Promise<string> {
   return await invoke('read_note', { filename, isDaily });
 }
 
+/**
+ * Reads a note along with its modification time, for passing to writeNote
+ * as expectedMtime so external edits aren't overwritten.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns The content and the mtime it was read at
+ * @throws {Error} If the file is binary rather than text
+ */
+export async function readNoteVersioned(filename: string, isDaily: boolean): Promise<VersionedNote> {
+  return await invoke('read_note_versioned', { filename, isDaily });
+}
+
+/**
+ * Gets the absolute filesystem path of a note, e.g. to open it in an external editor.
+ * Fails if the note doesn't exist.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns The absolute path
+ */
+export async function getNotePath(filename: string, isDaily: boolean): Promise<string> {
+  return await invoke('get_note_path', { filename, isDaily });
+}
+
+/**
+ * Shows a note in the system file manager (selected in Finder or Explorer;
+ * its folder is opened on Linux).
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ */
+export async function revealNoteInFinder(filename: string, isDaily: boolean): Promise<void> {
+  await invoke('reveal_note_in_finder', { filename, isDaily });
+}
+
 /**
  * Writes content to a note file, creating it if it doesn't exist.
  * @param filename - The note filename
  * @param content - The Markdown content to write
  * @param isDaily - Whether this is a daily note
+ * @param snapshot - Also keep a copy in the note's version history
+ * @param normalizeLineEndings - Convert CRLF to LF before writing (default true)
+ * @param expectedMtime - Only write if the note is unchanged since it was read
+ *   at this mtime (from readNoteVersioned or a previous writeNote)
+ * @returns The note's new mtime
+ * @throws {Error} Starting with "Conflict:" if the note changed since expectedMtime
+ */
+export async function writeNote(
+  filename: string,
+  content: string,
+  isDaily: boolean,
+  snapshot?: boolean,
+  normalizeLineEndings?: boolean,
+  expectedMtime?: number
+): Promise<number | null> {
+  return await invoke('write_note', {
+    filename,
+    content,
+    isDaily,
+    snapshot: snapshot || null,
+    normalizeLineEndings: normalizeLineEndings ?? null,
+    expectedMtime: expectedMtime ?? null,
+  });
+}
+
+/**
+ * Rewrites every note that uses CRLF line endings to use LF.
+ * @returns The number of notes changed
+ */
+export async function normalizeVaultLineEndings(): Promise<number> {
+  return await invoke('normalize_vault_line_endings');
+}
+
+/**
+ * Appends text to the end of a note without rewriting it, creating the note
+ * if it doesn't exist. A newline is added first if the note doesn't end with one.
+ * @param filename - The note filename
+ * @param text - Text to append
+ * @param isDaily - Whether this is a daily note
  */
-export async function writeNote(filename: string, content: string, isDaily: boolean): Promise<void> {
-  await invoke('write_note', { filename, content, isDaily });
+export async function appendToNote(filename: string, text: string, isDaily: boolean): Promise<void> {
+  await invoke('append_to_note', { filename, text, isDaily });
 }
 
 /**
- * Deletes a note file from the file system.
+ * Captures text into today's daily note, creating the note if needed.
+ * @param text - Text to capture
+ * @param heading - Add under this `## heading` (created if absent); appends to the end if omitted
+ */
+export async function captureToDaily(text: string, heading?: string): Promise<void> {
+  await invoke('capture_to_daily', { text, heading: heading || null });
+}
+
+/**
+ * Lists the notes linking to a note, for a confirmation before deleting it.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns How many notes link to it, and which
+ * @throws {Error} If the note doesn't exist
+ */
+export async function getDeleteImpact(filename: string, isDaily: boolean): Promise<DeleteImpact> {
+  return await invoke('get_delete_impact', { filename, isDaily });
+}
+
+/**
+ * Moves a note file into the trash folder.
  * @param filename - The note filename to delete
  * @param isDaily - Whether this is a daily note
  */
@@ -218,12 +465,49 @@ export async function deleteNote(filename: string, isDaily: boolean): Promise<vo
 }
 
 /**
- * Creates a new standalone note file.
+ * Moves several notes into the trash, continuing past any that fail.
+ * The batch is undone as a single step.
+ * @param refs - The notes to delete
+ * @returns Per-note results and how many were deleted
+ */
+export async function deleteNotes(refs: NoteRef[]): Promise<DeleteReport> {
+  return await invoke('delete_notes', { refs });
+}
+
+/**
+ * Creates a new standalone note file. Path separators and other characters
+ * not allowed in filenames are replaced with `-`.
  * @param title - The note title
+ * @param templateId - Template to start from (the default standalone template if omitted)
  * @returns The generated filename
  */
-export async function createNote(title: string): Promise<string> {
-  return await invoke('create_note', { title });
+export async function createNote(title: string, templateId?: string): Promise<string> {
+  return await invoke('create_note', { title, templateId: templateId || null });
+}
+
+/**
+ * Copies a note into a new standalone note. The first `# ` heading of the
+ * copy is updated to the new title.
+ * @param filename - Filename of the note to copy
+ * @param isDaily - Whether the source is a daily note
+ * @param newTitle - Title of the copy, used as its filename
+ * @returns The new note's filename
+ */
+export async function duplicateNote(filename: string, isDaily: boolean, newTitle: string): Promise<string> {
+  return await invoke('duplicate_note', { filename, isDaily, newTitle });
+}
+
+/**
+ * Imports the Markdown files in a folder into the vault. Names are
+ * slugified; a file whose name is taken is imported with a numeric suffix,
+ * or skipped if the existing note has identical content. Daily imports must
+ * be named `YYYY-MM-DD.md`.
+ * @param sourceDir - Absolute path of the folder to import from
+ * @param asDaily - Import into the daily folder instead of standalone notes
+ * @returns Which files were imported, skipped, or renamed
+ */
+export async function importNotes(sourceDir: string, asDaily: boolean): Promise<ImportReport> {
+  return await invoke('import_notes', { sourceDir, asDaily });
 }
 
 /**
@@ -237,11 +521,437 @@ export async function renameNote(oldFilename: string, newFilename: string, isDai
 }
 
 /**
- * Deletes all notes from the file system.
- * @throws {Error} If deletion fails
+ * Moves a note between the daily and standalone folders. Notes moved into
+ * daily must be named `YYYY-MM-DD.md`.
+ * @param filename - The note filename
+ * @param fromDaily - Whether the note is currently a daily note
+ * @param toDaily - Whether the note should become a daily note
+ * @returns The note's new vault-relative path (e.g., "daily/2025-01-01.md")
+ */
+export async function moveNote(filename: string, fromDaily: boolean, toDaily: boolean): Promise<string> {
+  return await invoke('move_note', { filename, fromDaily, toDaily });
+}
+
+/**
+ * Renames a note and rewrites wiki links in other notes that pointed at it.
+ * Piped display text and #heading suffixes are preserved. undoLast reverts
+ * the rename and the rewritten links together.
+ * @param oldFilename - Current filename
+ * @param newFilename - New filename
+ * @param isDaily - Whether this is a daily note
+ * @returns How many notes were updated, and any that couldn't be
+ */
+export async function renameNoteWithLinks(
+  oldFilename: string,
+  newFilename: string,
+  isDaily: boolean
+): Promise<RelinkReport> {
+  return await invoke('rename_note_with_links', { oldFilename, newFilename, isDaily });
+}
+
+/**
+ * Replaces text across notes, writing each changed note atomically.
+ * @param find - Text to search for (a regex when options.useRegex is set)
+ * @param replace - Replacement text; may use $1-style groups in regex mode
+ * @param options.useRegex - Treat `find` as a regular expression
+ * @param options.filenames - Notes to search (the whole vault if omitted)
+ * @param options.dryRun - Only count matches, without writing
+ * @returns Per-note replacement counts and the total
+ */
+export async function replaceInNotes(
+  find: string,
+  replace: string,
+  options: { useRegex?: boolean; filenames?: string[]; dryRun?: boolean } = {}
+): Promise<ReplaceReport> {
+  return await invoke('replace_in_notes', {
+    find,
+    replace,
+    useRegex: options.useRegex ?? false,
+    filenames: options.filenames || null,
+    dryRun: options.dryRun || null,
+  });
+}
+
+/**
+ * Moves all notes, or only daily or standalone ones, to the trash.
+ * The clear is undone as a single step.
+ * @param scope - Which notes to clear (defaults to 'All')
+ * @returns The number of notes cleared
+ * @throws {Error} If clearing fails
+ */
+export async function clearAllNotes(scope?: ClearScope): Promise<number> {
+  return await invoke('clear_all_notes', { scope: scope || null });
+}
+
+/**
+ * Starts a case-insensitive search of note titles and content. Results arrive
+ * as `search-hit` events (SearchHitEvent) tagged with the request id, followed
+ * by a `search-done` event (SearchDoneEvent).
+ * @param query - Text to search for, or a pattern when regex is set
+ * @param requestId - Identifies this search's events and lets it be cancelled
+ * @param regex - Treat the query as a regular expression, e.g. "TODO\\s*:"
+ * @throws {Error} If the query is empty or not a valid regex
+ */
+export async function searchNotesStreaming(query: string, requestId: string, regex?: boolean): Promise<void> {
+  await invoke('search_notes_streaming', { query, requestId, regex: regex || null });
+}
+
+/**
+ * Cancels a search started with searchNotesStreaming, e.g. when the query changes.
+ * @param requestId - The id the search was started with
+ */
+export async function cancelSearch(requestId: string): Promise<void> {
+  await invoke('cancel_search', { requestId });
+}
+
+/**
+ * Pins a note to the favorites section.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ */
+export async function pinNote(filename: string, isDaily: boolean): Promise<void> {
+  await invoke('pin_note', { filename, isDaily });
+}
+
+/**
+ * Removes a note from the favorites section.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ */
+export async function unpinNote(filename: string, isDaily: boolean): Promise<void> {
+  await invoke('unpin_note', { filename, isDaily });
+}
+
+/**
+ * Lists pinned notes in the order they were pinned.
+ * @returns Pinned note file metadata
+ */
+export async function listPinned(): Promise<NoteFile[]> {
+  return await invoke('list_pinned');
+}
+
+/**
+ * Lists the notes most recently opened, newest first. Notes that no longer
+ * exist are skipped.
+ * @param limit - Maximum number of notes to return
+ * @returns Recently opened note file metadata
+ */
+export async function listRecentNotes(limit: number): Promise<NoteFile[]> {
+  return await invoke('list_recent_notes', { limit });
+}
+
+/**
+ * Lists notes currently in the trash, newest first.
+ * @returns Trashed notes with their original location and deletion time
+ */
+export async function listTrash(): Promise<TrashEntry[]> {
+  return await invoke('list_trash');
+}
+
+/**
+ * Restores a trashed note to its original folder.
+ * @param trashFilename - The entry's trashFilename from listTrash
+ * @returns The restored note filename
+ */
+export async function restoreNote(trashFilename: string): Promise<string> {
+  return await invoke('restore_note', { trashFilename });
+}
+
+/**
+ * Permanently deletes everything in the trash.
+ */
+export async function emptyTrash(): Promise<void> {
+  await invoke('empty_trash');
+}
+
+/**
+ * Stores pasted bytes (e.g. an image) in the vault's assets folder.
+ * @param noteFilename - The note the attachment is pasted into
+ * @param data - The file contents
+ * @param extension - File extension without the dot, e.g. "png"
+ * @returns A markdown image linking to the attachment, ready to insert
+ */
+export async function saveAttachment(
+  noteFilename: string,
+  data: Uint8Array,
+  extension: string
+): Promise<string> {
+  return await invoke('save_attachment', { noteFilename, data: Array.from(data), extension });
+}
+
+/**
+ * Lists attachments in the assets folder that no note refers to.
+ * @returns Attachment filenames
+ */
+export async function listOrphanAttachments(): Promise<string[]> {
+  return await invoke('list_orphan_attachments');
+}
+
+/**
+ * Moves a note into the archive folder, out of the note list.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns The archived note's vault-relative path
+ */
+export async function archiveNote(filename: string, isDaily: boolean): Promise<string> {
+  return await invoke('archive_note', { filename, isDaily });
+}
+
+/**
+ * Moves an archived note back to the daily or standalone folder it came from.
+ * @param filename - The archived note filename
+ * @returns The restored note's vault-relative path
+ */
+export async function unarchiveNote(filename: string): Promise<string> {
+  return await invoke('unarchive_note', { filename });
+}
+
+/**
+ * Lists archived notes, which listNotes leaves out.
+ * @returns Archived note file metadata
+ */
+export async function listArchived(): Promise<NoteFile[]> {
+  return await invoke('list_archived');
+}
+
+/**
+ * Commits every change in the vault to its git repository, creating the
+ * repository on first use.
+ * @param message - Commit message (a timestamped default if omitted)
+ * @returns The new commit id, or null if nothing changed
+ */
+export async function commitVault(message?: string): Promise<string | null> {
+  return await invoke('commit_vault', { message: message || null });
+}
+
+/**
+ * Lists the commits that changed a note, newest first.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns Commits touching the note (empty if the vault isn't versioned yet)
+ */
+export async function noteHistory(filename: string, isDaily: boolean): Promise<Commit[]> {
+  return await invoke('note_history', { filename, isDaily });
+}
+
+/**
+ * Reads a note as it was at a given commit.
+ * @param filename - The note filename
+ * @param commit - Full or abbreviated commit id from noteHistory
+ * @returns The note content at that commit
+ */
+export async function getNoteAt(filename: string, commit: string): Promise<string> {
+  return await invoke('get_note_at', { filename, commit });
+}
+
+/**
+ * Lists the snapshots saved for a note with writeNote(..., snapshot), newest first.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns Saved versions of the note
+ */
+export async function listNoteVersions(filename: string, isDaily: boolean): Promise<NoteVersion[]> {
+  return await invoke('list_note_versions', { filename, isDaily });
+}
+
+/**
+ * Replaces a note with one of its snapshots. The current content is
+ * snapshotted first, so the restore itself can be reverted.
+ * @param filename - The note filename
+ * @param timestamp - The version's timestamp from listNoteVersions
+ * @param isDaily - Whether this is a daily note
+ * @returns The restored content
+ */
+export async function restoreNoteVersion(filename: string, timestamp: string, isDaily: boolean): Promise<string> {
+  return await invoke('restore_note_version', { filename, timestamp, isDaily });
+}
+
+/**
+ * Gets how note names from wiki links become filenames ('Slug' unless changed).
+ */
+export async function getNamingStrategy(): Promise<NamingStrategy> {
+  return await invoke('get_naming_strategy');
+}
+
+/**
+ * Sets how note names from wiki links become filenames. Links to notes named
+ * under an earlier strategy still resolve.
+ * @param strategy - 'Slug' (meeting-notes.md), 'Verbatim' (Meeting Notes.md) or 'Underscore' (Meeting_Notes.md)
+ */
+export async function setNamingStrategy(strategy: NamingStrategy): Promise<void> {
+  await invoke('set_naming_strategy', { strategy });
+}
+
+/**
+ * Gets how many snapshots are kept per note (20 unless changed).
+ */
+export async function getMaxNoteVersions(): Promise<number> {
+  return await invoke('get_max_note_versions');
+}
+
+/**
+ * Sets how many snapshots are kept per note; older ones are pruned on the next save.
+ * @param count - Number of versions to keep (at least 1)
+ */
+export async function setMaxNoteVersions(count: number): Promise<void> {
+  await invoke('set_max_note_versions', { count });
+}
+
+/**
+ * Reverses the most recent delete, rename, or clear operation.
+ * The undo log is persisted, so this works across restarts within a day.
+ * @returns Description of what was restored
+ * @throws {Error} If there is nothing to undo or the restore conflicts
+ */
+export async function undoLast(): Promise<UndoResult> {
+  return await invoke('undo_last');
+}
+
+/**
+ * Reverses the most recent delete, rename, move, or clear operation,
+ * like undoLast but without describing what was restored.
+ * @throws {Error} If there is nothing to undo or the restore conflicts
+ */
+export async function undoLastAction(): Promise<void> {
+  await invoke('undo_last_action');
+}
+
+/**
+ * Renders a note as a standalone HTML document with an embedded stylesheet.
+ * Wiki links become anchors to the resolved note files.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns The full HTML document as a string
+ */
+export async function exportNoteHtml(filename: string, isDaily: boolean): Promise<string> {
+  return await invoke('export_note_html', { filename, isDaily });
+}
+
+/**
+ * Exports a note and the notes it links to as one markdown document.
+ * Each note appears once under its own section, with a table of contents.
+ * @param filename - The starting note filename
+ * @param isDaily - Whether the starting note is a daily note
+ * @param depth - How many levels of links to follow
+ * @returns The combined markdown and the list of included notes
  */
-export async function clearAllNotes(): Promise<void> {
-  await invoke('clear_all_notes');
+export async function exportNoteBundle(filename: string, isDaily: boolean, depth: number): Promise<NoteBundle> {
+  return await invoke('export_note_bundle', { filename, isDaily, depth });
+}
+
+/**
+ * Writes a note to a PDF file at the given path.
+ * Wiki links are printed as plain text with numbered references at the end.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @param destPath - Where to write the PDF
+ */
+export async function exportNotePdf(filename: string, isDaily: boolean, destPath: string): Promise<void> {
+  await invoke('export_note_pdf', { filename, isDaily, destPath });
+}
+
+/**
+ * Builds a nested markdown list of links to every heading in a note.
+ * Headings in code blocks are skipped and repeated headings get unique anchors.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns The table of contents as markdown
+ */
+export async function generateToc(filename: string, isDaily: boolean): Promise<string> {
+  return await invoke('generate_toc', { filename, isDaily });
+}
+
+/**
+ * Lists a note's headings, ATX (# Title) and setext (underlined) alike,
+ * skipping frontmatter and code blocks.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns Headings in order, with 1-based line numbers
+ */
+export async function getNoteOutline(filename: string, isDaily: boolean): Promise<Heading[]> {
+  return await invoke('get_note_outline', { filename, isDaily });
+}
+
+/**
+ * Computes stats for unsaved editor content without touching the file, so
+ * they can update live as the user types.
+ * @param content - The current editor content
+ * @param options - What to leave out of the counts; frontmatter and code by default
+ * @returns Word, character, line, heading and link counts
+ */
+export async function analyzeContent(content: string, options?: WordCountOptions): Promise<ContentStats> {
+  return await invoke('analyze_content', { content, options: options || null });
+}
+
+/**
+ * Computes word, character, and line counts plus reading time for a note.
+ * Markdown syntax is stripped so counts reflect the prose.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @param options - What to leave out of the counts; frontmatter and code by default
+ * @returns Note statistics
+ */
+export async function noteStats(
+  filename: string,
+  isDaily: boolean,
+  options?: WordCountOptions
+): Promise<NoteStats> {
+  return await invoke('note_stats', { filename, isDaily, options: options || null });
+}
+
+/**
+ * Computes totals for the whole vault: note counts, words, distinct tags
+ * and the range of dates covered by daily notes.
+ * @param options - What to leave out of the word count; frontmatter and code by default
+ * @returns Vault statistics
+ */
+export async function vaultStats(options?: WordCountOptions): Promise<VaultStats> {
+  return await invoke('vault_stats', { options: options || null });
+}
+
+/**
+ * Lists every tag used across the vault with the number of notes using it.
+ * Includes frontmatter tags and inline #hashtags outside code blocks.
+ * @returns Tags sorted by usage, most used first
+ */
+export async function listTags(): Promise<TagCount[]> {
+  return await invoke('list_tags');
+}
+
+/**
+ * Lists the notes that contain a given tag.
+ * @param tag - The tag to filter by, with or without the leading "#"
+ * @returns Matching note file metadata
+ */
+export async function notesByTag(tag: string): Promise<NoteFile[]> {
+  return await invoke('notes_by_tag', { tag });
+}
+
+/**
+ * Reads a note's YAML frontmatter as a property map.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns Frontmatter properties (empty if the note has none)
+ */
+export async function getFrontmatter(filename: string, isDaily: boolean): Promise<Record<string, unknown>> {
+  return await invoke('get_frontmatter', { filename, isDaily });
+}
+
+/**
+ * Merges properties into a note's frontmatter without touching the body.
+ * Keys set to null are removed; unspecified keys are preserved.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @param props - Properties to set or remove
+ * @param snapshot - Also keep a copy in the note's version history
+ */
+export async function setFrontmatter(
+  filename: string,
+  isDaily: boolean,
+  props: Record<string, unknown>,
+  snapshot?: boolean
+): Promise<void> {
+  await invoke('set_frontmatter', { filename, isDaily, props, snapshot: snapshot || null });
 }
 
 /**
@@ -298,6 +1008,122 @@ export function filenameToNote(file: NoteFile, content: string): Note {
   };
 }
 
+/**
+ * Builds the note link graph for visualization. Edges are directed and
+ * deduplicated; self-links are left out.
+ * @param includeUnresolved - Also include links to missing notes as dangling nodes
+ * @param includeMarkdownLinks - Also count `[text](note.md)` links as edges
+ * @returns Graph nodes (one per note) and edges between them
+ */
+export async function getLinkGraph(
+  includeUnresolved?: boolean,
+  includeMarkdownLinks?: boolean
+): Promise<LinkGraph> {
+  return await invoke('get_link_graph', {
+    includeUnresolved: includeUnresolved || null,
+    includeMarkdownLinks: includeMarkdownLinks || null,
+  });
+}
+
+/**
+ * Rebuilds the backend's cached link index from disk.
+ * Useful after notes were changed outside the app.
+ */
+export async function rebuildLinkIndex(): Promise<void> {
+  await invoke('rebuild_link_index');
+}
+
+/**
+ * Finds notes with no incoming or outgoing wiki links.
+ * Daily notes left as empty template scaffolding are included too.
+ * @returns Orphaned note file metadata
+ */
+export async function findOrphans(): Promise<NoteFile[]> {
+  return await invoke('find_orphans');
+}
+
+/**
+ * Finds wiki links whose target note doesn't exist, across the whole vault.
+ * Results are ordered by source note; repeated links in a note appear once.
+ * @returns Broken links with surrounding context
+ */
+export async function findBrokenLinks(): Promise<BrokenLink[]> {
+  return await invoke('find_broken_links');
+}
+
+/**
+ * Finds groups of notes with near-identical content, most similar first.
+ * @param threshold - Similarity (0-1) at which two notes count as duplicates
+ * @returns Groups of duplicate notes with their similarity
+ */
+export async function findDuplicateNotes(threshold: number): Promise<DuplicateGroup[]> {
+  return await invoke('find_duplicate_notes', { threshold });
+}
+
+/**
+ * Lists the wiki links going out of a saved note, resolved with existence flags.
+ * @param filename - The note filename
+ * @param isDaily - Whether this is a daily note
+ * @returns The note's links, in the order they appear
+ */
+export async function getOutgoingLinks(filename: string, isDaily: boolean): Promise<WikiLink[]> {
+  return await invoke('get_outgoing_links', { filename, isDaily });
+}
+
+/**
+ * Parses and resolves the wiki links in editor content against note names
+ * the caller already has, without checking the disk for each link. Headings
+ * aren't looked up, so headingFound is always null.
+ * @param content - The content to scan
+ * @param existingNames - Existing note filenames, e.g. the names from listNotes
+ * @returns The links, in the order they appear
+ */
+export async function scanNoteLinksWithIndex(content: string, existingNames: string[]): Promise<WikiLink[]> {
+  return await invoke('scan_note_links_with_index', { content, existingNames });
+}
+
+/**
+ * Counts the other notes linking to a note, e.g. for a sidebar badge.
+ * Cheaper than fetching backlinks, since no note content is read.
+ * @param filename - The note filename
+ * @param includeMarkdownLinks - Also count `[text](note.md)` links
+ * @returns Number of linking notes
+ */
+export async function countBacklinks(filename: string, includeMarkdownLinks?: boolean): Promise<number> {
+  return await invoke('count_backlinks', { filename, includeMarkdownLinks: includeMarkdownLinks || null });
+}
+
+/**
+ * Suggests existing note names for wiki link autocomplete.
+ * Matches filenames and first-heading titles case-insensitively by prefix.
+ * @param prefix - The text typed after "[["
+ * @returns Up to 20 note names, best matches and newest notes first
+ */
+export async function suggestLinks(prefix: string): Promise<string[]> {
+  return await invoke('suggest_links', { prefix });
+}
+
+/**
+ * Inlines the notes referenced by ![[Note]] (or ![[Note#Section]]) embeds.
+ * Embeds of missing notes become a visible placeholder; embeds nested deeper
+ * than `depth`, or that would embed a note in itself, are left as written.
+ * @param content - Note content containing embeds
+ * @param depth - Maximum number of nested embed levels to inline
+ * @returns The content with embeds replaced
+ */
+export async function resolveEmbeds(content: string, depth: number): Promise<string> {
+  return await invoke('resolve_embeds', { content, depth });
+}
+
+/**
+ * Resolves a note name like a wiki link would, without reading the note.
+ * @param noteName - The note name to check
+ * @returns The resolved filename, whether it exists and whether it's a daily note
+ */
+export async function getNoteExistence(noteName: string): Promise<NoteExistence> {
+  return await invoke<NoteExistence>('check_note_exists', { noteName });
+}
+
 /**
  * Checks if a note with the given name exists in the file system.
  * @param noteName - The note name to check
@@ -305,8 +1131,8 @@ export function filenameToNote(file: NoteFile, content: string): Note {
  */
 export async function checkNoteExists(noteName: string): Promise<boolean> {
   try {
-    const result = await invoke<[boolean, string]>('note_exists', { noteName });
-    return result[0];
+    const result = await getNoteExistence(noteName);
+    return result.exists;
   } catch {
     return false;
   }
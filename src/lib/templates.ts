@@ -1,12 +1,32 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Template, SaveTemplateInput } from '@/types/template';
+import type { ImportReport } from '@/types/note';
+import type { Period, Template, SaveTemplateInput, TemplateConflict } from '@/types/template';
 
 /**
  * Retrieves all available templates from the backend.
+ * @param category - Only return templates in this category (case-insensitive)
  * @returns Array of template objects
  */
-export async function listTemplates(): Promise<Template[]> {
-  return await invoke<Template[]>('list_templates');
+export async function listTemplates(category?: string): Promise<Template[]> {
+  return await invoke<Template[]>('list_templates', { category: category || null });
+}
+
+/**
+ * Reports template ids shared by several files in the templates folder,
+ * or by a file and a default template. Defaults take precedence; among
+ * files, the one named `<id>.json` is used.
+ * @returns Conflicting ids with the files involved
+ */
+export async function validateTemplates(): Promise<TemplateConflict[]> {
+  return await invoke<TemplateConflict[]>('validate_templates');
+}
+
+/**
+ * Lists the distinct template categories, sorted by name.
+ * @returns Category names
+ */
+export async function listTemplateCategories(): Promise<string[]> {
+  return await invoke<string[]>('list_template_categories');
 }
 
 /**
@@ -27,6 +47,16 @@ export async function saveTemplate(input: SaveTemplateInput): Promise<Template>
   return await invoke<Template>('save_template', { input });
 }
 
+/**
+ * Copies a template, default or custom, into a new custom template.
+ * @param id - Template ID to copy
+ * @param newName - Name for the copy; fails if a template already uses it
+ * @returns The new template
+ */
+export async function duplicateTemplate(id: string, newName: string): Promise<Template> {
+  return await invoke<Template>('duplicate_template', { id, newName });
+}
+
 /**
  * Updates an existing template.
  * @param id - Template ID to update
@@ -37,6 +67,16 @@ export async function updateTemplate(id: string, input: SaveTemplateInput): Prom
   return await invoke<Template>('update_template', { id, input });
 }
 
+/**
+ * Lists the built-in template variables (used as `{{name}}`).
+ * Saving a template with any other variable fails unless
+ * `allowCustomVariables` is set.
+ * @returns Variable names, sorted
+ */
+export async function listTemplateVariables(): Promise<string[]> {
+  return await invoke<string[]>('list_template_variables');
+}
+
 /**
  * Deletes a template.
  * @param id - Template ID to delete
@@ -45,13 +85,63 @@ export async function deleteTemplate(id: string): Promise<void> {
   await invoke('delete_template', { id });
 }
 
+/**
+ * Writes all custom templates to a single JSON file.
+ * Default templates are built in and are not exported.
+ * @param destPath - Where to write the file
+ * @returns How many templates were exported
+ */
+export async function exportTemplates(destPath: string): Promise<number> {
+  return await invoke<number>('export_templates', { destPath });
+}
+
+/**
+ * Loads templates from a file written by `exportTemplates`.
+ * Templates whose id is already in use are skipped, not overwritten.
+ * @param sourcePath - The exported templates file
+ * @returns Imported and skipped template ids
+ */
+export async function importTemplates(sourcePath: string): Promise<ImportReport> {
+  return await invoke<ImportReport>('import_templates', { sourcePath });
+}
+
 /**
  * Processes a template with variable substitutions.
+ * Unknown {{placeholders}} are left intact.
  * @param templateId - The template ID to apply
+ * @param variables - Optional custom variables, overriding built-ins
  * @returns The processed template content with variables replaced
  */
-export async function applyTemplate(templateId: string): Promise<string> {
-  return await invoke<string>('apply_template', { templateId });
+export async function applyTemplate(
+  templateId: string,
+  variables?: Record<string, string>
+): Promise<string> {
+  return await invoke<string>('apply_template', { templateId, variables: variables || null });
+}
+
+/**
+ * Renders a snippet template for inserting at the cursor: variables are
+ * filled in as in applyTemplate, and frontmatter and the trailing newline
+ * are dropped.
+ * @param templateId - The snippet template ID
+ * @param variables - Optional custom variables, overriding built-ins
+ * @returns The text to insert
+ */
+export async function renderSnippet(
+  templateId: string,
+  variables?: Record<string, string>
+): Promise<string> {
+  return await invoke<string>('render_snippet', { templateId, variables: variables || null });
+}
+
+/**
+ * Lists the distinct {{variable}} names a template uses, in order of first
+ * appearance, so custom ones can be filled in before applying it.
+ * @param templateId - The template ID to inspect
+ * @returns Variable names without the braces, built-ins included
+ */
+export async function templateVariablesUsed(templateId: string): Promise<string[]> {
+  return await invoke<string[]>('template_variables_used', { templateId });
 }
 
 /**
@@ -67,3 +157,44 @@ export async function createNoteFromTemplate(
 ): Promise<void> {
   await invoke('create_note_from_template', { filename, templateId, isDaily });
 }
+
+/**
+ * Gets the template new notes start from when created without one.
+ * @param isDaily - Whether to get the daily or the standalone default
+ * @returns The template id, or null when new notes start empty
+ */
+export async function getDefaultTemplate(isDaily: boolean): Promise<string | null> {
+  return await invoke<string | null>('get_default_template', { isDaily });
+}
+
+/**
+ * Sets the template new daily or standalone notes start from when created
+ * without one. An explicit template passed at creation still takes precedence.
+ * @param isDaily - Whether to set the daily or the standalone default
+ * @param templateId - Template id, or null to start new notes empty
+ * @throws {Error} If the template doesn't exist or is a snippet
+ */
+export async function setDefaultTemplate(isDaily: boolean, templateId: string | null): Promise<void> {
+  await invoke('set_default_template', { isDaily, templateId: templateId || null });
+}
+
+/**
+ * Creates today's daily note (YYYY-MM-DD.md) if it doesn't exist yet.
+ * Returns the existing filename without error when it already exists.
+ * @param templateId - Template to scaffold the note with (the default daily template if omitted)
+ * @returns Today's daily note filename
+ */
+export async function createDailyNote(templateId?: string): Promise<string> {
+  return await invoke<string>('create_daily_note', { templateId: templateId || null });
+}
+
+/**
+ * Creates this week's (YYYY-Www.md) or this month's (YYYY-MM.md) note in the
+ * periodic folder if it doesn't exist yet.
+ * @param period - 'Weekly' or 'Monthly'
+ * @param templateId - Optional template to scaffold the note with
+ * @returns The periodic note filename
+ */
+export async function createPeriodicNote(period: Period, templateId?: string): Promise<string> {
+  return await invoke<string>('create_periodic_note', { period, templateId: templateId || null });
+}
@@ -25,4 +25,5 @@ export type CalendarPermission =
   | 'Restricted'
   | 'Denied'
   | 'Authorized'
-  | 'FullAccess';
+  | 'FullAccess'
+  | 'Unavailable'; // No calendar to read, e.g. no .ics file set on Linux/Windows
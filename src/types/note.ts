@@ -12,7 +12,246 @@ export interface NoteFile {
   name: string;
   path: string;
   isDaily: boolean;
-  date?: string;
+  date?: string; // YYYY-MM-DD from a daily filename or a `date` frontmatter field
+  title?: string; // From YAML frontmatter, if present
+  tags?: string[];
+  isPinned?: boolean;
+  preview?: string | null; // Start of the body text, when requested from listNotesPage
+  modified?: string | null; // ISO-8601 UTC modification time
+  created?: string | null; // ISO-8601 UTC creation time, when the filesystem records one
+}
+
+export type NoteSortKey = 'Name' | 'Modified' | 'Created';
+
+export interface NotePage {
+  notes: NoteFile[];
+  total: number; // Total number of notes before pagination
 }
 
 export type NoteType = 'daily' | 'standalone';
+
+export interface NoteBundle {
+  content: string;
+  included: string[]; // Filenames in the order they appear in the bundle
+}
+
+export interface TagCount {
+  tag: string;
+  count: number;
+}
+
+export interface TrashEntry {
+  trashFilename: string; // Relative to the trash folder, e.g. "notes/my-note.20240614-153012.md"
+  originalFilename: string;
+  originalPath: string;
+  isDaily: boolean;
+  deletedAt?: string;
+}
+
+export interface UndoResult {
+  description: string;
+  restored: string[];
+}
+
+export interface Heading {
+  level: number;
+  text: string;
+  line: number; // 1-based
+}
+
+export interface NoteStats {
+  wordCount: number;
+  charCount: number;
+  lineCount: number;
+  readingTimeMinutes: number;
+}
+
+export interface ContentStats extends NoteStats {
+  headingCount: number;
+  linkCount: number; // Wiki and markdown links, not images
+}
+
+/** What word counts leave out; unset fields use the defaults noted. */
+export interface WordCountOptions {
+  excludeFrontmatter?: boolean; // Default true
+  excludeCode?: boolean; // Fenced code blocks, default true
+  excludeWikiLinks?: boolean; // Default false: links count as their display text
+}
+
+export interface VaultStats {
+  noteCount: number;
+  dailyCount: number;
+  standaloneCount: number;
+  wordCount: number;
+  tagCount: number; // Distinct tags
+  averageWordCount: number;
+  firstDaily?: string | null; // YYYY-MM-DD
+  lastDaily?: string | null;
+}
+
+export interface NoteExistence {
+  filename: string;
+  exists: boolean;
+  isDaily: boolean;
+}
+
+export interface DayCount {
+  date: string; // YYYY-MM-DD
+  wordCount: number;
+}
+
+export interface VersionedNote {
+  content: string;
+  mtime?: number | null; // Milliseconds; null while the note doesn't exist yet
+}
+
+export type NamingStrategy = 'Slug' | 'Verbatim' | 'Underscore';
+
+export type Direction = 'Previous' | 'Next';
+
+export type ClearScope = 'All' | 'Daily' | 'Standalone';
+
+/** Identifies a note for commands that act on several at once. */
+export interface NoteRef {
+  filename: string;
+  isDaily: boolean;
+}
+
+export interface DeleteOutcome {
+  filename: string;
+  isDaily: boolean;
+  error?: string | null; // Set when this note couldn't be deleted
+}
+
+export interface DeleteImpact {
+  count: number;
+  linkingNotes: NoteRef[];
+}
+
+export interface DeleteReport {
+  results: DeleteOutcome[]; // In request order
+  deleted: number;
+}
+
+/** Payload of the `stats-updated` event emitted after each `write_note`. */
+export interface StatsUpdatedEvent {
+  filename: string;
+  isDaily: boolean;
+  wordCount: number;
+  charCount: number;
+  lineCount: number;
+}
+
+/** Payload of the `note-changed` event emitted when notes change on disk. */
+export interface NoteChangedEvent {
+  path: string; // Relative to the vault root, e.g. "daily/2025-01-01.md"
+  kind: 'created' | 'modified' | 'deleted';
+  isDaily: boolean;
+}
+
+/** Payload of the `scan-progress` events emitted while long commands scan the vault. */
+export interface ScanProgressEvent {
+  scan: string; // Command doing the scan, e.g. "find_broken_links"
+  processed: number;
+  total: number;
+}
+
+/** Payload of the `search-hit` events emitted by `searchNotesStreaming`, one per matching note. */
+export interface SearchHitEvent {
+  requestId: string;
+  filename: string;
+  isDaily: boolean;
+  title: string;
+  titleMatch: boolean;
+  snippet: string; // Context around the first content match, empty for title-only hits
+  matchStart: number | null; // Byte range of the match within snippet
+  matchEnd: number | null;
+}
+
+/** Payload of the `search-done` event that ends a streaming search. */
+export interface SearchDoneEvent {
+  requestId: string;
+  total: number;
+  cancelled: boolean;
+}
+
+export interface RenamedImport {
+  source: string; // Filename in the source folder
+  filename: string; // Filename the note was imported as
+}
+
+export interface ImportReport {
+  imported: string[];
+  skipped: string[];
+  renamed: RenamedImport[];
+}
+
+/** A wiki link in a note, resolved against the vault. */
+export interface WikiLink {
+  text: string; // Note name as written
+  display: string; // Text shown for the link: before the `|`, or the whole link
+  target: string; // Filename the link resolves (or would resolve) to
+  exists: boolean;
+  heading?: string | null;
+  headingFound?: boolean | null; // Whether `heading` is in the target note
+  suggestion?: string | null; // Closest existing note, for unresolved links
+}
+
+export interface DuplicateGroup {
+  notes: NoteRef[];
+  similarity: number; // 0-1, lowest between the pairs that formed the group
+}
+
+export interface BrokenLink {
+  fromNote: string;
+  isDaily: boolean;
+  link: string; // As written, without any #heading suffix
+  context: string;
+}
+
+export interface GraphNode {
+  id: string; // Vault-relative path, e.g. "notes/my-note.md"
+  title: string;
+  isDaily: boolean;
+  exists: boolean; // False for dangling nodes from unresolved links
+}
+
+export interface GraphEdge {
+  from: string;
+  to: string;
+}
+
+export interface LinkGraph {
+  nodes: GraphNode[];
+  edges: GraphEdge[];
+}
+
+export interface FileReplacement {
+  filename: string;
+  isDaily: boolean;
+  count: number;
+}
+
+export interface RelinkReport {
+  updated: number; // Notes whose links now point at the new name
+  failed: string[]; // Notes linking to the old name that couldn't be rewritten
+}
+
+export interface ReplaceReport {
+  files: FileReplacement[]; // Notes with at least one match
+  total: number;
+  dryRun: boolean; // True when nothing was written
+}
+
+export interface Commit {
+  id: string;
+  message: string;
+  author: string;
+  time: string; // ISO-8601 UTC
+}
+
+export interface NoteVersion {
+  timestamp: string; // Identifies the version for restoreNoteVersion
+  savedAt?: string | null;
+  size: number;
+}
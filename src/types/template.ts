@@ -5,6 +5,8 @@ export interface Template {
   icon: string;
   isDefault: boolean;
   content: string;
+  category?: string | null; // Uncategorized when unset
+  isSnippet: boolean; // Inserted at the cursor, not offered for new notes
 }
 
 export interface TemplateFile {
@@ -19,4 +21,16 @@ export interface SaveTemplateInput {
   description: string;
   icon: string;
   content: string;
+  category?: string | null;
+  isSnippet?: boolean;
+  allowCustomVariables?: boolean; // Skip the unknown {{variable}} check
 }
+
+/** Template files that share an id; only the first listed is used. */
+export interface TemplateConflict {
+  id: string;
+  files: string[];
+  shadowsDefault: boolean; // A default template owns the id and wins
+}
+
+export type Period = 'Weekly' | 'Monthly';